@@ -0,0 +1,5 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+pub mod turing;
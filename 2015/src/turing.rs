@@ -0,0 +1,374 @@
+//! The computer of day 23, opening the Turing lock
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::convert::From;
+use std::error::Error;
+use std::fmt;
+
+pub type Register = char;
+pub type Offset = i32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Hlf(Register),
+    Tpl(Register),
+    Inc(Register),
+    Jmp(Offset),
+    Jie(Register, Offset),
+    Jio(Register, Offset),
+}
+
+impl Instruction {
+    pub fn parse(s: &str) -> Result<Self, Box<dyn Error>> {
+        lazy_static! {
+            static ref RE: Regex =
+                Regex::new(r"^([a-z]{3}) ([a-z]|[+-]\d+)(?:, ([+-]\d+))?$").unwrap();
+        }
+
+        macro_rules! parse_register {
+            ($str: expr) => {
+                $str.ok_or("No register specified")?
+                    .chars()
+                    .next()
+                    .ok_or("Invalid register name")?
+            };
+        }
+
+        macro_rules! parse_offset {
+            ($str: expr) => {
+                $str.ok_or("No offset specified")?.parse::<Offset>()?
+            };
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            match caps.at(1).ok_or("No first group")? {
+                "hlf" => Ok(Instruction::Hlf(parse_register!(caps.at(2)))),
+                "tpl" => Ok(Instruction::Tpl(parse_register!(caps.at(2)))),
+                "inc" => Ok(Instruction::Inc(parse_register!(caps.at(2)))),
+                "jmp" => Ok(Instruction::Jmp(parse_offset!(caps.at(2)))),
+                "jie" => Ok(Instruction::Jie(
+                    parse_register!(caps.at(2)),
+                    parse_offset!(caps.at(3)),
+                )),
+                "jio" => Ok(Instruction::Jio(
+                    parse_register!(caps.at(2)),
+                    parse_offset!(caps.at(3)),
+                )),
+                name => Err(From::from(format!("Unknown instruction {}", name))),
+            }
+        } else {
+            Err(From::from("Does not match regex"))
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Instruction::Hlf(r) => write!(f, "hlf {}", r),
+            Instruction::Tpl(r) => write!(f, "tpl {}", r),
+            Instruction::Inc(r) => write!(f, "inc {}", r),
+            Instruction::Jmp(offset) => write!(f, "jmp {:+}", offset),
+            Instruction::Jie(r, offset) => write!(f, "jie {}, {:+}", r, offset),
+            Instruction::Jio(r, offset) => write!(f, "jio {}, {:+}", r, offset),
+        }
+    }
+}
+
+/// Loop counting in `counter` the steps of the Collatz sequence starting at `value`, until it
+/// reaches 1:
+///
+/// ```text
+/// jio value, +8
+/// inc counter
+/// jie value, +4
+/// tpl value
+/// inc value
+/// jmp +2
+/// hlf value
+/// jmp -7
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollatzLoop {
+    /// Index of the first instruction of the loop
+    pub start: usize,
+    pub value: Register,
+    pub counter: Register,
+}
+
+impl CollatzLoop {
+    pub fn find(instructions: &[Instruction]) -> Option<Self> {
+        instructions.windows(8).enumerate().find_map(|(start, window)| {
+            match *window {
+                [Instruction::Jio(v1, 8), Instruction::Inc(counter), Instruction::Jie(v2, 4), Instruction::Tpl(v3), Instruction::Inc(v4), Instruction::Jmp(2), Instruction::Hlf(v5), Instruction::Jmp(-7)]
+                    if [v2, v3, v4, v5].iter().all(|&v| v == v1) && counter != v1 =>
+                {
+                    Some(CollatzLoop {
+                        start,
+                        value: v1,
+                        counter,
+                    })
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Number of steps of the Collatz sequence starting at `n`, and number of instructions
+    /// executed by the loop when `value` starts at `n`. `None` if the loop never ends (when
+    /// starting at 0) or if `value` would overflow.
+    pub fn length(mut n: u64) -> Option<(u64, u64)> {
+        if n == 0 {
+            return None;
+        }
+        let mut steps: u64 = 0;
+        // The last `jio`, exiting the loop
+        let mut instructions: u64 = 1;
+        while n != 1 {
+            if n.is_multiple_of(2) {
+                n /= 2;
+                instructions = instructions.checked_add(5)?;
+            } else {
+                n = n.checked_mul(3)?.checked_add(1)?;
+                instructions = instructions.checked_add(7)?;
+            }
+            steps += 1;
+        }
+        Some((steps, instructions))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionError {
+    LimitReached,
+    /// A register overflowed, at the instruction with this index
+    Overflow(usize),
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExecutionError::LimitReached => write!(f, "step limit reached"),
+            ExecutionError::Overflow(index) => {
+                write!(f, "register overflow at instruction {}", index)
+            }
+        }
+    }
+}
+
+impl Error for ExecutionError {}
+
+#[derive(Debug, Default)]
+pub struct Computer {
+    pub registers: HashMap<char, u64>,
+    /// Number of instructions executed
+    pub steps: u64,
+    /// Maximum number of instructions to execute
+    pub limit: Option<u64>,
+    /// Print register changes on stderr
+    pub trace: bool,
+}
+
+impl Computer {
+    pub fn get(&self, register: Register) -> u64 {
+        self.registers.get(&register).cloned().unwrap_or(0)
+    }
+
+    fn set(&mut self, index: usize, instruction: Instruction, register: Register, value: u64) {
+        if self.trace {
+            eprintln!(
+                "{:4} {:12} {}: {} -> {}",
+                index,
+                instruction.to_string(),
+                register,
+                self.get(register),
+                value
+            );
+        }
+        self.registers.insert(register, value);
+    }
+
+    fn run(
+        &mut self,
+        instructions: &[Instruction],
+        collatz_loop: Option<CollatzLoop>,
+    ) -> Result<(), ExecutionError> {
+        let mut index: i32 = 0;
+
+        while index >= 0 && index < instructions.len() as i32 {
+            let instruction = instructions[index as usize];
+
+            // Skip the loop by computing the Collatz sequence length. The skipped instructions
+            // are counted, and the loop is executed normally if they would reach the limit.
+            if let Some(collatz_loop) = collatz_loop {
+                if index as usize == collatz_loop.start {
+                    let value = self.get(collatz_loop.value);
+                    let skip = CollatzLoop::length(value).and_then(|(steps, instructions)| {
+                        let total = self.steps.checked_add(instructions)?;
+                        let counter = self.get(collatz_loop.counter).checked_add(steps)?;
+                        if self.limit.is_some_and(|limit| total > limit) {
+                            return None;
+                        }
+                        Some((total, counter))
+                    });
+                    if let Some((total, counter)) = skip {
+                        self.steps = total;
+                        self.set(index as usize, instruction, collatz_loop.counter, counter);
+                        self.set(index as usize, instruction, collatz_loop.value, 1);
+                        index += 8;
+                        continue;
+                    }
+                }
+            }
+
+            if self.limit.is_some_and(|limit| self.steps >= limit) {
+                return Err(ExecutionError::LimitReached);
+            }
+            self.steps += 1;
+
+            match instruction {
+                Instruction::Hlf(r) => {
+                    let value = self.get(r) / 2;
+                    self.set(index as usize, instruction, r, value);
+                    index += 1;
+                }
+                Instruction::Tpl(r) => {
+                    let value = self
+                        .get(r)
+                        .checked_mul(3)
+                        .ok_or(ExecutionError::Overflow(index as usize))?;
+                    self.set(index as usize, instruction, r, value);
+                    index += 1;
+                }
+                Instruction::Inc(r) => {
+                    let value = self
+                        .get(r)
+                        .checked_add(1)
+                        .ok_or(ExecutionError::Overflow(index as usize))?;
+                    self.set(index as usize, instruction, r, value);
+                    index += 1;
+                }
+                Instruction::Jmp(offset) => {
+                    index += offset;
+                }
+                Instruction::Jie(r, offset) => {
+                    index += if self.get(r).is_multiple_of(2) {
+                        offset
+                    } else {
+                        1
+                    }
+                }
+                Instruction::Jio(r, offset) => index += if self.get(r) == 1 { offset } else { 1 },
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute the instructions until the program exits
+    pub fn execute(&mut self, instructions: &[Instruction]) -> Result<(), ExecutionError> {
+        self.run(instructions, None)
+    }
+
+    /// Same as `execute`, but the Collatz loop, if any, is not executed: its result is computed
+    /// directly
+    pub fn execute_fast(&mut self, instructions: &[Instruction]) -> Result<(), ExecutionError> {
+        self.run(instructions, CollatzLoop::find(instructions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<Instruction> {
+        input
+            .lines()
+            .map(|line| Instruction::parse(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let instructions = parse("inc a\njio a, +2\ntpl a\ninc a");
+
+        let mut computer = Computer::default();
+        computer.execute(&instructions).unwrap();
+        assert_eq!(computer.registers.get(&'a'), Some(&2));
+        assert_eq!(computer.steps, 3);
+        assert_eq!(instructions[1].to_string(), "jio a, +2");
+    }
+
+    #[test]
+    fn limit() {
+        let mut computer = Computer {
+            limit: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            computer.execute(&parse("inc a\njmp -1")),
+            Err(ExecutionError::LimitReached)
+        );
+        assert_eq!(computer.get('a'), 5);
+    }
+
+    #[test]
+    fn collatz() {
+        let instructions = parse(include_str!("../tests/day23.input"));
+        assert_eq!(
+            CollatzLoop::find(&instructions),
+            Some(CollatzLoop {
+                start: 38,
+                value: 'a',
+                counter: 'b',
+            })
+        );
+        assert_eq!(CollatzLoop::length(6), Some((8, 45)));
+        assert_eq!(CollatzLoop::length(0), None);
+        assert_eq!(CollatzLoop::length(u64::MAX), None);
+
+        for &a in &[0, 1] {
+            let mut slow = Computer::default();
+            slow.registers.insert('a', a);
+            slow.execute(&instructions).unwrap();
+            let mut fast = Computer::default();
+            fast.registers.insert('a', a);
+            fast.execute_fast(&instructions).unwrap();
+            assert_eq!(fast.registers, slow.registers);
+            assert_eq!(fast.steps, slow.steps);
+        }
+
+        // The limit applies to the skipped loop too
+        let mut slow = Computer {
+            limit: Some(500),
+            ..Default::default()
+        };
+        assert_eq!(
+            slow.execute(&instructions),
+            Err(ExecutionError::LimitReached)
+        );
+        let mut fast = Computer {
+            limit: Some(500),
+            ..Default::default()
+        };
+        assert_eq!(
+            fast.execute_fast(&instructions),
+            Err(ExecutionError::LimitReached)
+        );
+        assert_eq!(fast.registers, slow.registers);
+        assert_eq!(fast.steps, 500);
+    }
+
+    #[test]
+    fn overflow() {
+        let instructions = parse(include_str!("../tests/day23.input"));
+        let mut slow = Computer::default();
+        slow.registers.insert('a', u64::MAX);
+        let result = slow.execute(&instructions);
+        assert!(matches!(result, Err(ExecutionError::Overflow(_))));
+        let mut fast = Computer::default();
+        fast.registers.insert('a', u64::MAX);
+        assert_eq!(fast.execute_fast(&instructions), result);
+        assert_eq!(fast.registers, slow.registers);
+    }
+}
@@ -0,0 +1,348 @@
+//! Assembunny interpreter, shared by days 12, 23, 25 and the bonus puzzle
+
+pub mod clock;
+pub mod optimizer;
+
+use optimizer::Shortcut;
+use std::convert::TryFrom;
+use std::fmt;
+
+pub type Register = u8;
+
+pub const REGISTERS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Value {
+    FromRegister(Register),
+    Const(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Cpy(Value, Value),
+    Inc(Value),
+    Dec(Value),
+    Jnz(Value, Value),
+    Tgl(Value),
+    Out(Value),
+}
+
+impl Instruction {
+    /// Arguments are kept as they are, so a toggled instruction may be invalid (like `cpy 1 2`).
+    /// Invalid instructions are skipped when executed.
+    pub fn toggle(&mut self) {
+        *self = match *self {
+            Instruction::Inc(a) => Instruction::Dec(a),
+            Instruction::Dec(a) | Instruction::Tgl(a) | Instruction::Out(a) => Instruction::Inc(a),
+            Instruction::Jnz(a, b) => Instruction::Cpy(a, b),
+            Instruction::Cpy(a, b) => Instruction::Jnz(a, b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid instruction on line {}: {:?}",
+            self.line, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+named!(
+    parse_number<i32>,
+    do_parse!(
+        minus: opt!(tag!("-"))
+            >> n: take_while1!(nom::is_digit)
+            >> (if minus == Some(b"-") { -1 } else { 1 }
+                * n.iter()
+                    .fold(0, |acc, item| acc * 10 + i32::from(item - b'0')))
+    )
+);
+
+named!(
+    parse_register<Register>,
+    map!(one_of!("abcd"), |c: char| c as u8 - b'a')
+);
+
+named!(
+    parse_value<Value>,
+    alt!(map!(parse_number, Value::Const) | map!(parse_register, Value::FromRegister))
+);
+
+named!(
+    parse_cpy<Instruction>,
+    do_parse!(
+        tag!("cpy ") >> n: parse_value >> tag!(" ") >> m: parse_value >> (Instruction::Cpy(n, m))
+    )
+);
+
+named!(
+    parse_inc<Instruction>,
+    do_parse!(tag!("inc ") >> n: parse_value >> (Instruction::Inc(n)))
+);
+
+named!(
+    parse_dec<Instruction>,
+    do_parse!(tag!("dec ") >> n: parse_value >> (Instruction::Dec(n)))
+);
+
+named!(
+    parse_jnz<Instruction>,
+    do_parse!(
+        tag!("jnz ") >> n: parse_value >> tag!(" ") >> m: parse_value >> (Instruction::Jnz(n, m))
+    )
+);
+
+named!(
+    parse_tgl<Instruction>,
+    do_parse!(tag!("tgl ") >> n: parse_value >> (Instruction::Tgl(n)))
+);
+
+named!(
+    parse_out<Instruction>,
+    do_parse!(tag!("out ") >> n: parse_value >> (Instruction::Out(n)))
+);
+
+named!(
+    parse_line<Instruction>,
+    do_parse!(
+        res: alt!( parse_cpy | parse_inc | parse_dec | parse_jnz | parse_tgl | parse_out ) >>
+        tag!("\n") >>
+        (res)
+    )
+);
+
+/// Parse a program, one instruction per line
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(
+            |(index, line)| match parse_line(format!("{}\n", line).as_bytes()) {
+                nom::IResult::Done(&[], instruction) => Ok(instruction),
+                _ => Err(ParseError {
+                    line: index + 1,
+                    text: line.to_string(),
+                }),
+            },
+        )
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitReached;
+
+impl fmt::Display for LimitReached {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "step limit reached")
+    }
+}
+
+impl std::error::Error for LimitReached {}
+
+#[derive(Debug, Clone)]
+pub struct Vm {
+    pub program: Vec<Instruction>,
+    pub registers: [i32; REGISTERS],
+    /// Index of the next instruction. The program halts when it points outside of the program.
+    pub ptr: isize,
+    /// Number of instructions executed so far
+    pub steps: u64,
+    pub limit: Option<u64>,
+    /// Loops to shortcut, when the program is optimized
+    shortcuts: Option<Vec<Option<Shortcut>>>,
+}
+
+impl Vm {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Vm {
+            program,
+            registers: [0; REGISTERS],
+            ptr: 0,
+            steps: 0,
+            limit: None,
+            shortcuts: None,
+        }
+    }
+
+    /// Execute add and multiply loops in a single step, see `optimizer`. Registers and step counts
+    /// are the same as when executing the loops instruction by instruction.
+    pub fn optimized(mut self) -> Self {
+        self.shortcuts = Some(optimizer::optimize(&self.program));
+        self
+    }
+
+    /// Stop running after `limit` steps
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn get(&self, value: Value) -> i32 {
+        match value {
+            Value::FromRegister(r) => self.registers[r as usize],
+            Value::Const(c) => c,
+        }
+    }
+
+    fn instruction_index(&self, ptr: isize) -> Option<usize> {
+        usize::try_from(ptr)
+            .ok()
+            .filter(|&index| index < self.program.len())
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.instruction_index(self.ptr).is_none()
+    }
+
+    /// Next instruction to execute, unless the program halted
+    pub fn instruction(&self) -> Option<Instruction> {
+        Some(self.program[self.instruction_index(self.ptr)?])
+    }
+
+    pub fn is_limit_reached(&self) -> bool {
+        self.limit.is_some_and(|limit| self.steps >= limit)
+    }
+
+    /// Execute the next instruction, and return the value it outputs, if any
+    pub fn step(&mut self) -> Option<i32> {
+        let index = self.instruction_index(self.ptr)?;
+        if let Some(shortcut) = self.shortcut(index) {
+            shortcut.apply(&mut self.registers);
+            self.ptr += shortcut.instruction_count() as isize;
+            return None;
+        }
+
+        let instruction = self.program[index];
+        let mut offset = 1;
+        let mut output = None;
+        match instruction {
+            Instruction::Cpy(v, Value::FromRegister(r)) => self.registers[r as usize] = self.get(v),
+            Instruction::Inc(Value::FromRegister(r)) => self.registers[r as usize] += 1,
+            Instruction::Dec(Value::FromRegister(r)) => self.registers[r as usize] -= 1,
+            Instruction::Jnz(a, b) => {
+                if self.get(a) != 0 {
+                    offset = self.get(b) as isize;
+                }
+            }
+            Instruction::Tgl(v) => {
+                if let Some(index) = self.instruction_index(self.ptr + self.get(v) as isize) {
+                    self.program[index].toggle();
+                    if self.shortcuts.is_some() {
+                        self.shortcuts = Some(optimizer::optimize(&self.program));
+                    }
+                }
+            }
+            Instruction::Out(v) => output = Some(self.get(v)),
+            // Invalid instructions produced by `tgl`
+            Instruction::Cpy(_, Value::Const(_))
+            | Instruction::Inc(Value::Const(_))
+            | Instruction::Dec(Value::Const(_)) => {}
+        }
+        self.ptr += offset;
+        self.steps += 1;
+        output
+    }
+
+    /// Shortcut of the loop starting at `index`, if it can be taken without exceeding the step
+    /// limit. Steps are counted here.
+    fn shortcut(&mut self, index: usize) -> Option<Shortcut> {
+        let shortcut = (*self.shortcuts.as_ref()?.get(index)?)?;
+        let steps = self.steps + shortcut.cost(&self.registers)?;
+        if self.limit.is_some_and(|limit| steps > limit) {
+            return None;
+        }
+        self.steps = steps;
+        Some(shortcut)
+    }
+
+    /// Run until the program halts (`Ok(None)`) or outputs a value
+    pub fn resume(&mut self) -> Result<Option<i32>, LimitReached> {
+        while !self.is_halted() {
+            if self.is_limit_reached() {
+                return Err(LimitReached);
+            }
+            if let Some(value) = self.step() {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Run until the program halts, ignoring its output
+    pub fn run(&mut self) -> Result<(), LimitReached> {
+        while self.resume()?.is_some() {}
+        Ok(())
+    }
+
+    /// Values output by the program, until it halts or reaches the step limit
+    pub fn output(&mut self) -> Output<'_> {
+        Output { vm: self }
+    }
+}
+
+pub struct Output<'a> {
+    vm: &'a mut Vm,
+}
+
+impl Iterator for Output<'_> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        self.vm.resume().ok().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse("cpy 41 a\njnz a -2\nout b\n"),
+            Ok(vec![
+                Instruction::Cpy(Value::Const(41), Value::FromRegister(0)),
+                Instruction::Jnz(Value::FromRegister(0), Value::Const(-2)),
+                Instruction::Out(Value::FromRegister(1)),
+            ])
+        );
+        assert_eq!(
+            parse("inc a\ncpy a e\n"),
+            Err(ParseError {
+                line: 2,
+                text: "cpy a e".to_string()
+            })
+        );
+        assert!(parse("inc a b").is_err());
+    }
+
+    #[test]
+    fn toggle_test() {
+        // Example of day 23
+        let program = parse("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a").unwrap();
+        let mut vm = Vm::new(program);
+        vm.run().unwrap();
+        assert_eq!(vm.registers[0], 3);
+        assert_eq!(vm.program[4], parse("jnz 1 a").unwrap()[0]);
+    }
+
+    #[test]
+    fn limit_test() {
+        let program = parse("out a\ninc a\njnz 1 -2").unwrap();
+        let mut vm = Vm::new(program).with_limit(10);
+        assert_eq!(vm.output().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(vm.run(), Err(LimitReached));
+        assert_eq!(vm.steps, 10);
+    }
+}
@@ -0,0 +1,127 @@
+//! Analysis of the signal output by a program, like the clock signal of day 25
+
+use super::{Instruction, LimitReached, Vm, REGISTERS};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Signal {
+    /// The program halted after outputting these values
+    Finite(Vec<i32>),
+    /// `prefix`, then `pattern` repeated forever
+    Periodic { prefix: Vec<i32>, pattern: Vec<i32> },
+}
+
+impl Signal {
+    pub fn period(&self) -> Option<usize> {
+        match self {
+            Signal::Finite(_) => None,
+            Signal::Periodic { pattern, .. } => Some(pattern.len()),
+        }
+    }
+
+    /// Whether the signal is 0, 1, 0, 1... forever
+    pub fn is_clock(&self) -> bool {
+        match self {
+            Signal::Finite(_) => false,
+            Signal::Periodic { prefix, pattern } => {
+                pattern.len() % 2 == 0
+                    && prefix
+                        .iter()
+                        .chain(pattern)
+                        .enumerate()
+                        .all(|(index, &value)| value == index as i32 % 2)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |values: &[i32]| {
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        match self {
+            Signal::Finite(values) => write!(f, "{} then halts", join(values)),
+            Signal::Periodic { prefix, pattern } => {
+                if !prefix.is_empty() {
+                    write!(f, "{} then ", join(prefix))?;
+                }
+                write!(f, "{} repeated (period {})", join(pattern), pattern.len())
+            }
+        }
+    }
+}
+
+/// Run the program until it is in the same state (registers, instruction pointer and program) on
+/// two `out` instructions. Everything it outputs between them is then repeated forever.
+pub fn analyze(vm: &mut Vm) -> Result<Signal, LimitReached> {
+    let mut outputs = Vec::new();
+    let mut seen: HashMap<([i32; REGISTERS], isize, Vec<Instruction>), usize> = HashMap::new();
+
+    loop {
+        match vm.instruction() {
+            None => return Ok(Signal::Finite(outputs)),
+            Some(Instruction::Out(_)) => {
+                let state = (vm.registers, vm.ptr, vm.program.clone());
+                if let Some(&start) = seen.get(&state) {
+                    let pattern = outputs.split_off(start);
+                    return Ok(Signal::Periodic {
+                        prefix: outputs,
+                        pattern,
+                    });
+                }
+                seen.insert(state, outputs.len());
+            }
+            Some(_) => {}
+        }
+        if vm.is_limit_reached() {
+            return Err(LimitReached);
+        }
+        outputs.extend(vm.step());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembunny::parse;
+
+    fn signal(input: &str, a: i32) -> Result<Signal, LimitReached> {
+        let mut vm = Vm::new(parse(input).unwrap())
+            .optimized()
+            .with_limit(1_000_000);
+        vm.registers[0] = a;
+        analyze(&mut vm)
+    }
+
+    #[test]
+    fn analyze_test() {
+        assert_eq!(signal("out a\nout 1", 0), Ok(Signal::Finite(vec![0, 1])));
+        let periodic = signal("out 5\nout a\njnz 1 -1", 2).unwrap();
+        assert_eq!(
+            periodic,
+            Signal::Periodic {
+                prefix: vec![5],
+                pattern: vec![2],
+            }
+        );
+        assert_eq!(periodic.to_string(), "5 then 2 repeated (period 1)");
+        assert_eq!(signal("inc a\njnz 1 -1", 0), Err(LimitReached));
+    }
+
+    #[test]
+    fn clock_test() {
+        let input = include_str!("../../tests/day25/input");
+        let clock = signal(input, 198).unwrap();
+        assert!(clock.is_clock());
+        assert_eq!(clock.period(), Some(12));
+        let signal = signal(input, 0).unwrap();
+        assert!(!signal.is_clock());
+        assert_eq!(signal.period(), Some(12));
+    }
+}
@@ -0,0 +1,224 @@
+//! Peephole optimizer replacing the add and multiply loops of assembunny programs. Shortcuts are
+//! attached to the first instruction of a loop, and the program itself is left unchanged so jump
+//! and `tgl` offsets stay valid.
+
+use super::{Instruction, Register, Value, REGISTERS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    /// `inc target` (or `dec target` when `sign` is -1), `dec source`, `jnz source -2`, in any
+    /// order of the first two instructions
+    Add {
+        target: Register,
+        sign: i32,
+        source: Register,
+    },
+    /// `cpy factor temp`, an add loop from `temp` to `target`, `dec counter`, `jnz counter -5`
+    Mul {
+        target: Register,
+        sign: i32,
+        factor: Value,
+        temp: Register,
+        counter: Register,
+    },
+}
+
+impl Shortcut {
+    /// Number of instructions of the loop
+    pub fn instruction_count(&self) -> usize {
+        match self {
+            Shortcut::Add { .. } => 3,
+            Shortcut::Mul { .. } => 6,
+        }
+    }
+
+    /// Number of steps the loop takes when executed naively, or `None` if its counter is not
+    /// positive, in which case the loop runs until the counter overflows and must not be shortcut
+    pub fn cost(&self, registers: &[i32; REGISTERS]) -> Option<u64> {
+        match *self {
+            Shortcut::Add { source, .. } => {
+                let count = registers[source as usize];
+                if count > 0 {
+                    Some(3 * count as u64)
+                } else {
+                    None
+                }
+            }
+            Shortcut::Mul {
+                factor, counter, ..
+            } => {
+                let factor = match factor {
+                    Value::FromRegister(r) => registers[r as usize],
+                    Value::Const(c) => c,
+                };
+                let count = registers[counter as usize];
+                if factor > 0 && count > 0 {
+                    Some(count as u64 * (3 * factor as u64 + 3))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Set the registers as they are when the loop exits
+    pub fn apply(&self, registers: &mut [i32; REGISTERS]) {
+        match *self {
+            Shortcut::Add {
+                target,
+                sign,
+                source,
+            } => {
+                registers[target as usize] += sign * registers[source as usize];
+                registers[source as usize] = 0;
+            }
+            Shortcut::Mul {
+                target,
+                sign,
+                factor,
+                temp,
+                counter,
+            } => {
+                let factor = match factor {
+                    Value::FromRegister(r) => registers[r as usize],
+                    Value::Const(c) => c,
+                };
+                registers[target as usize] += sign * factor * registers[counter as usize];
+                registers[temp as usize] = 0;
+                registers[counter as usize] = 0;
+            }
+        }
+    }
+}
+
+fn add_loop(instructions: &[Instruction]) -> Option<Shortcut> {
+    let (target, sign, source) = match *instructions.get(..3)? {
+        [Instruction::Inc(Value::FromRegister(target)), Instruction::Dec(Value::FromRegister(source)), Instruction::Jnz(Value::FromRegister(jump), Value::Const(-2))]
+        | [Instruction::Dec(Value::FromRegister(source)), Instruction::Inc(Value::FromRegister(target)), Instruction::Jnz(Value::FromRegister(jump), Value::Const(-2))]
+            if jump == source =>
+        {
+            (target, 1, source)
+        }
+        [Instruction::Dec(Value::FromRegister(target)), Instruction::Dec(Value::FromRegister(source)), Instruction::Jnz(Value::FromRegister(jump), Value::Const(-2))]
+            if jump == source =>
+        {
+            (target, -1, source)
+        }
+        _ => return None,
+    };
+    if target == source {
+        return None;
+    }
+    Some(Shortcut::Add {
+        target,
+        sign,
+        source,
+    })
+}
+
+fn mul_loop(instructions: &[Instruction]) -> Option<Shortcut> {
+    let (factor, temp) = match *instructions.first()? {
+        Instruction::Cpy(factor, Value::FromRegister(temp)) => (factor, temp),
+        _ => return None,
+    };
+    let (target, sign) = match add_loop(instructions.get(1..)?)? {
+        Shortcut::Add {
+            target,
+            sign,
+            source,
+        } if source == temp => (target, sign),
+        _ => return None,
+    };
+    let counter = match *instructions.get(4..6)? {
+        [Instruction::Dec(Value::FromRegister(counter)), Instruction::Jnz(Value::FromRegister(jump), Value::Const(-5))]
+            if jump == counter =>
+        {
+            counter
+        }
+        _ => return None,
+    };
+    if counter == temp || counter == target || factor == Value::FromRegister(counter) {
+        return None;
+    }
+    if factor == Value::FromRegister(temp) || factor == Value::FromRegister(target) {
+        return None;
+    }
+    Some(Shortcut::Mul {
+        target,
+        sign,
+        factor,
+        temp,
+        counter,
+    })
+}
+
+/// Shortcut starting at each instruction of a program, if any. This has to be computed again when
+/// a `tgl` changes the program.
+pub fn optimize(program: &[Instruction]) -> Vec<Option<Shortcut>> {
+    (0..program.len())
+        .map(|index| mul_loop(&program[index..]).or_else(|| add_loop(&program[index..])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembunny::{parse, Vm};
+
+    #[test]
+    fn optimize_test() {
+        let program = parse("cpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5").unwrap();
+        let shortcuts = optimize(&program);
+        assert_eq!(
+            shortcuts[0],
+            Some(Shortcut::Mul {
+                target: 0,
+                sign: 1,
+                factor: Value::FromRegister(1),
+                temp: 2,
+                counter: 3,
+            })
+        );
+        assert_eq!(
+            shortcuts[1],
+            Some(Shortcut::Add {
+                target: 0,
+                sign: 1,
+                source: 2,
+            })
+        );
+        assert_eq!(shortcuts[2..], [None, None, None, None]);
+
+        // The loop would modify its own counter
+        assert_eq!(optimize(&parse("inc a\ndec a\njnz a -2").unwrap())[0], None);
+    }
+
+    fn assert_same_execution(input: &str, registers: [i32; REGISTERS]) {
+        let program = parse(input).unwrap();
+        let mut naive = Vm::new(program.clone());
+        naive.registers = registers;
+        naive.run().unwrap();
+
+        let mut optimized = Vm::new(program).optimized();
+        optimized.registers = registers;
+        optimized.run().unwrap();
+
+        assert_eq!(optimized.registers, naive.registers);
+        assert_eq!(optimized.program, naive.program);
+        assert_eq!(optimized.steps, naive.steps);
+    }
+
+    #[test]
+    fn same_execution_test() {
+        for a in 6..=8 {
+            assert_same_execution(include_str!("../../tests/day23/input"), [a, 0, 0, 0]);
+        }
+        assert_same_execution(include_str!("../../tests/day12/input"), [0, 0, 0, 0]);
+        assert_same_execution(
+            "cpy 3 b\ncpy a c\ndec d\ndec c\njnz c -2\ndec b\njnz b -5",
+            [4, 0, 9, 0],
+        );
+        // The add loop is toggled into `dec a` before running
+        assert_same_execution("tgl 1\ninc a\ndec b\njnz b -2", [2, 5, 0, 0]);
+    }
+}
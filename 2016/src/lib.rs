@@ -0,0 +1,4 @@
+#[macro_use]
+extern crate nom;
+
+pub mod assembunny;
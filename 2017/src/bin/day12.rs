@@ -1,24 +1,11 @@
+extern crate aoc;
 extern crate regex;
+use aoc::union_find::UnionFind;
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::io::{stdin, BufRead};
 
-type Program = u32;
-
-fn populate_group(
-    programs: &HashMap<Program, Vec<Program>>,
-    mut group: &mut HashSet<Program>,
-    program: Program,
-) {
-    if group.contains(&program) {
-        return;
-    }
-
-    group.insert(program);
-    for other in &programs[&program] {
-        populate_group(programs, &mut group, *other);
-    }
-}
+type Program = usize;
 
 fn main() {
     let stdin = stdin();
@@ -37,21 +24,12 @@ fn main() {
         })
         .collect::<HashMap<Program, Vec<Program>>>();
 
-    {
-        let mut group = HashSet::new();
-        populate_group(&programs, &mut group, 0);
-        println!("Part 1: {}", group.len());
-    }
-
-    {
-        let mut in_group = HashSet::new();
-        let mut count = 0;
-        for program in programs.keys() {
-            if !in_group.contains(program) {
-                count += 1;
-                populate_group(&programs, &mut in_group, *program);
-            }
+    let mut groups = UnionFind::new(programs.len());
+    for (program, others) in &programs {
+        for other in others {
+            groups.union(*program, *other);
         }
-        println!("Part 2: {}", count);
     }
+    println!("Part 1: {}", groups.component_size(0));
+    println!("Part 2: {}", groups.component_count());
 }
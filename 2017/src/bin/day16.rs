@@ -1,3 +1,6 @@
+extern crate aoc;
+
+use aoc::cycle;
 use std::io::{stdin, Read};
 
 #[derive(Debug)]
@@ -57,24 +60,14 @@ fn main() {
         println!("Part 1: {}", first_round.iter().collect::<String>());
     }
 
-    {
-        let mut billion_rounds = programs.clone();
-        let mut period = 0;
-        let first = programs.clone();
-
-        for _ in 0..1_000_000_000 {
-            apply_actions(&actions, &mut billion_rounds);
-            period += 1;
-            if billion_rounds == first {
-                break;
-            }
-        }
-
-        // Take a shortcut!
-        for _ in 0..(1_000_000_000 % period) {
-            apply_actions(&actions, &mut billion_rounds);
-        }
-
-        println!("Part 2: {}", billion_rounds.iter().collect::<String>());
-    }
+    let billion_rounds = cycle::nth_state(
+        programs,
+        |programs| {
+            let mut programs = programs.clone();
+            apply_actions(&actions, &mut programs);
+            programs
+        },
+        1_000_000_000,
+    );
+    println!("Part 2: {}", billion_rounds.iter().collect::<String>());
 }
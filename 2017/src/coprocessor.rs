@@ -0,0 +1,316 @@
+//! Analysis of the coprocessor programs of day 23: instruction profiling, and recognition of the
+//! loop counting composite numbers, which is way too slow to run when `a` is 1
+
+use machine::{fetch, parse, Instruction, Integer, Register, Registers, Value};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coprocessor {
+    pub registers: Registers,
+    pub pointer: Integer,
+    /// Number of times each instruction was executed
+    pub counts: Vec<u64>,
+}
+
+impl Coprocessor {
+    pub fn new(program: &[Instruction], a: Integer) -> Self {
+        let mut registers = Registers::new();
+        registers.insert('a', a);
+        Coprocessor {
+            registers,
+            pointer: 0,
+            counts: vec![0; program.len()],
+        }
+    }
+
+    /// Execute the next instruction, and return `false` if the program terminated. The
+    /// coprocessor doesn't support `snd` and `rcv`: the program stops before them.
+    pub fn step(&mut self, program: &[Instruction]) -> bool {
+        let instruction = match fetch(program, self.pointer) {
+            Some(instruction) => instruction,
+            None => return false,
+        };
+        let offset = match instruction.execute(&mut self.registers) {
+            Some(offset) => offset,
+            None => return false,
+        };
+        self.counts[self.pointer as usize] += 1;
+        self.pointer += offset;
+        true
+    }
+
+    /// Run until the instruction at `pointer` is about to be executed, at most `limit` steps, and
+    /// return whether it was reached
+    pub fn run_until(&mut self, program: &[Instruction], pointer: Integer, limit: u64) -> bool {
+        for _ in 0..limit {
+            if self.pointer == pointer {
+                return true;
+            }
+            if !self.step(program) {
+                return false;
+            }
+        }
+        self.pointer == pointer
+    }
+
+    pub fn run(&mut self, program: &[Instruction]) {
+        while self.step(program) {}
+    }
+
+    /// Number of `mul` instructions executed
+    pub fn mul_count(&self, program: &[Instruction]) -> u64 {
+        program
+            .iter()
+            .zip(&self.counts)
+            .filter(|&(instruction, _)| matches!(*instruction, Instruction::Mul(_, _)))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Loops jumping back from `end` to `start`, the most iterated first
+    pub fn hot_loops(&self, program: &[Instruction]) -> Vec<HotLoop> {
+        let mut loops: Vec<_> = program
+            .iter()
+            .enumerate()
+            .filter_map(|(end, instruction)| match *instruction {
+                Instruction::Jnz(_, Value::Integer(offset))
+                | Instruction::Jgz(_, Value::Integer(offset))
+                    if offset <= 0 && end as Integer + offset >= 0 =>
+                {
+                    let start = (end as Integer + offset) as usize;
+                    Some(HotLoop {
+                        start,
+                        end,
+                        iterations: self.counts[end],
+                        executions: self.counts[start..=end].iter().sum(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        loops.sort_by_key(|hot_loop| Reverse(hot_loop.iterations));
+        loops
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotLoop {
+    pub start: usize,
+    pub end: usize,
+    /// Number of times the jump back was executed, taken or not
+    pub iterations: u64,
+    /// Number of instructions executed inside the loop, including nested loops
+    pub executions: u64,
+}
+
+/// Loop counting in `counter` the composite numbers from `from` to `to` included, by steps of
+/// `step`, testing all products `d * e` with `d` and `e` from 2 to the number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositeLoop {
+    /// First instruction of the loop
+    pub start: usize,
+    pub from: Register,
+    pub to: Register,
+    pub step: Integer,
+    pub counter: Register,
+}
+
+/// The loop of the day 23 programs. Registers are renamed, and the step (`-17`) can be any value.
+const COMPOSITE_LOOP: &str = "set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -17
+jnz 1 -23";
+
+/// Index of the `sub b -17` line in `COMPOSITE_LOOP`
+const STEP_LINE: usize = 22;
+
+/// Registers of the template bound to the registers of a program, both ways
+#[derive(Default)]
+struct Bindings(HashMap<Register, Register>, HashMap<Register, Register>);
+
+impl Bindings {
+    fn register(&mut self, template: Register, actual: Register) -> bool {
+        *self.0.entry(template).or_insert(actual) == actual
+            && *self.1.entry(actual).or_insert(template) == template
+    }
+
+    fn value(&mut self, template: Value, actual: Value) -> bool {
+        match (template, actual) {
+            (Value::Register(template), Value::Register(actual)) => self.register(template, actual),
+            (Value::Integer(template), Value::Integer(actual)) => template == actual,
+            _ => false,
+        }
+    }
+
+    fn instruction(&mut self, template: Instruction, actual: Instruction) -> bool {
+        use machine::Instruction::*;
+        match (template, actual) {
+            (Set(tx, ty), Set(ax, ay))
+            | (Sub(tx, ty), Sub(ax, ay))
+            | (Mul(tx, ty), Mul(ax, ay)) => self.register(tx, ax) && self.value(ty, ay),
+            (Jnz(tx, ty), Jnz(ax, ay)) => self.value(tx, ax) && self.value(ty, ay),
+            _ => false,
+        }
+    }
+}
+
+pub fn composite_loop(program: &[Instruction]) -> Option<CompositeLoop> {
+    let template = parse(COMPOSITE_LOOP).unwrap();
+    (0..program.len()).find_map(|start| {
+        let lines = program.get(start..start + template.len())?;
+        let mut bindings = Bindings::default();
+        let step = match lines[STEP_LINE] {
+            Instruction::Sub(_, Value::Integer(step)) => -step,
+            _ => return None,
+        };
+        let matches = template
+            .iter()
+            .zip(lines)
+            .enumerate()
+            .all(|(index, (t, a))| {
+                if index == STEP_LINE {
+                    match *a {
+                        Instruction::Sub(register, _) => bindings.register('b', register),
+                        _ => false,
+                    }
+                } else {
+                    bindings.instruction(*t, *a)
+                }
+            });
+        if matches && step > 0 {
+            Some(CompositeLoop {
+                start,
+                from: bindings.0[&'b'],
+                to: bindings.0[&'c'],
+                step,
+                counter: bindings.0[&'h'],
+            })
+        } else {
+            None
+        }
+    })
+}
+
+fn is_composite(n: Integer) -> bool {
+    let mut d = 2;
+    while d * d <= n {
+        if n % d == 0 {
+            return true;
+        }
+        d += 1;
+    }
+    false
+}
+
+/// Value of the counter register when the program terminates, computed by running it until the
+/// composite loop starts, and counting the composite numbers of its range. `None` if the program
+/// doesn't have such a loop, or if the loop never terminates.
+pub fn count_composites(program: &[Instruction], a: Integer) -> Option<Integer> {
+    let composite_loop = composite_loop(program)?;
+    let mut coprocessor = Coprocessor::new(program, a);
+    if !coprocessor.run_until(program, composite_loop.start as Integer, 1_000_000) {
+        return None;
+    }
+    let register = |register| Value::Register(register).as_integer(&coprocessor.registers);
+    let (from, to) = (register(composite_loop.from), register(composite_loop.to));
+    if to < from || (to - from) % composite_loop.step != 0 {
+        return None;
+    }
+    let count = (0..=(to - from) / composite_loop.step)
+        .map(|index| from + index * composite_loop.step)
+        .filter(|&n| is_composite(n))
+        .count();
+    Some(register(composite_loop.counter) + count as Integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../tests/day23/input");
+
+    #[test]
+    fn composite_loop_test() {
+        let program = parse(INPUT).unwrap();
+        assert_eq!(
+            composite_loop(&program),
+            Some(CompositeLoop {
+                start: 8,
+                from: 'b',
+                to: 'c',
+                step: 17,
+                counter: 'h',
+            })
+        );
+
+        // Same loop with renamed registers and a smaller range, which can be run
+        let renamed: String = INPUT
+            .lines()
+            .map(|line| {
+                let tokens: Vec<_> = line
+                    .split(' ')
+                    .map(|token| match token {
+                        "b" => "y",
+                        "h" => "x",
+                        "100" => "1",
+                        "-100000" => "0",
+                        "-17000" => "-170",
+                        token => token,
+                    })
+                    .collect();
+                tokens.join(" ") + "\n"
+            })
+            .collect();
+        let program = parse(&renamed).unwrap();
+        let composite_loop = composite_loop(&program).unwrap();
+        assert_eq!((composite_loop.from, composite_loop.counter), ('y', 'x'));
+        let mut coprocessor = Coprocessor::new(&program, 1);
+        coprocessor.run(&program);
+        assert_eq!(
+            count_composites(&program, 1),
+            Some(coprocessor.registers[&'x'])
+        );
+    }
+
+    #[test]
+    fn profile_test() {
+        let program = parse(INPUT).unwrap();
+        let mut coprocessor = Coprocessor::new(&program, 0);
+        coprocessor.run(&program);
+        assert_eq!(coprocessor.mul_count(&program), 3025);
+        assert_eq!(coprocessor.hot_loops(&program)[0].start, 11);
+        assert_eq!(count_composites(&program, 0), Some(1));
+        assert_eq!(count_composites(&program, 1), Some(915));
+    }
+
+    #[test]
+    fn unsupported_test() {
+        let program = parse("set a 3\nsnd a\nset a 4").unwrap();
+        let mut coprocessor = Coprocessor::new(&program, 0);
+        coprocessor.run(&program);
+        assert_eq!(coprocessor.pointer, 1);
+        assert_eq!(coprocessor.registers[&'a'], 3);
+        assert_eq!(coprocessor.counts, vec![1, 0, 0]);
+    }
+}
@@ -0,0 +1,343 @@
+//! Duet programs of day 18, playing sounds or exchanging values between several processes
+
+use machine::{fetch, Instruction, Integer, Registers, Value};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// How `snd` and `rcv` are understood
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// `snd` plays a sound, and `rcv` recovers the last sound played if its register isn't 0
+    Sound,
+    /// `snd` sends a value to another process, and `rcv` waits for a value to be received
+    SendReceive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Executed,
+    Sent(Integer),
+    Recovered(Integer),
+    /// Waiting on a `rcv` with no value received, nothing was executed
+    Blocked,
+    /// The instruction pointer is outside of the program, nothing was executed
+    Terminated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub id: Integer,
+    pub registers: Registers,
+    pub pointer: Integer,
+    /// Number of values sent, or sounds played
+    pub sent: usize,
+    /// Last sound played
+    pub sound: Option<Integer>,
+    /// Values received and not yet read by `rcv`
+    pub inbox: VecDeque<Integer>,
+}
+
+impl Process {
+    /// New process, with its id in the register `p`
+    pub fn new(id: Integer) -> Self {
+        let mut registers = Registers::new();
+        registers.insert('p', id);
+        Process {
+            id,
+            registers,
+            pointer: 0,
+            sent: 0,
+            sound: None,
+            inbox: VecDeque::new(),
+        }
+    }
+
+    pub fn is_terminated(&self, program: &[Instruction]) -> bool {
+        fetch(program, self.pointer).is_none()
+    }
+
+    /// Whether the next instruction is a `rcv` that would block
+    pub fn is_waiting(&self, program: &[Instruction]) -> bool {
+        match fetch(program, self.pointer) {
+            Some(Instruction::Rcv(_)) => self.inbox.is_empty(),
+            _ => false,
+        }
+    }
+
+    pub fn step(&mut self, program: &[Instruction], interpretation: Interpretation) -> Event {
+        let instruction = match fetch(program, self.pointer) {
+            Some(instruction) => instruction,
+            None => return Event::Terminated,
+        };
+        let mut event = Event::Executed;
+        match instruction {
+            Instruction::Snd(value) => {
+                let integer = value.as_integer(&self.registers);
+                self.sent += 1;
+                match interpretation {
+                    Interpretation::Sound => self.sound = Some(integer),
+                    Interpretation::SendReceive => event = Event::Sent(integer),
+                }
+            }
+            Instruction::Rcv(register) => match interpretation {
+                Interpretation::Sound => {
+                    if Value::Register(register).as_integer(&self.registers) != 0 {
+                        if let Some(sound) = self.sound {
+                            event = Event::Recovered(sound);
+                        }
+                    }
+                }
+                Interpretation::SendReceive => match self.inbox.pop_front() {
+                    Some(integer) => {
+                        self.registers.insert(register, integer);
+                    }
+                    None => return Event::Blocked,
+                },
+            },
+            _ => {
+                self.pointer += instruction.execute(&mut self.registers).unwrap();
+                return event;
+            }
+        }
+        self.pointer += 1;
+        event
+    }
+}
+
+/// First sound recovered by a program, if it recovers any before terminating
+pub fn play(program: &[Instruction]) -> Option<Integer> {
+    let mut process = Process::new(0);
+    loop {
+        match process.step(program, Interpretation::Sound) {
+            Event::Recovered(sound) => return Some(sound),
+            Event::Terminated => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Processes after running a duet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub processes: Vec<Process>,
+    /// Whether the processes stopped because they were all waiting on a `rcv`, some of them
+    /// possibly being terminated
+    pub deadlock: bool,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}",
+            if self.deadlock {
+                "deadlock"
+            } else {
+                "terminated"
+            }
+        )?;
+        for process in &self.processes {
+            let mut registers: Vec<_> = process.registers.iter().collect();
+            registers.sort();
+            let registers: Vec<_> = registers
+                .iter()
+                .map(|&(register, value)| format!("{}={}", register, value))
+                .collect();
+            writeln!(
+                f,
+                "program {}: sent {}, registers {}",
+                process.id,
+                process.sent,
+                registers.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Run `count` processes of the same program, each sending its values to the next one, and the
+/// last one to the first one, until none of them can continue
+pub fn run(program: &[Instruction], count: usize) -> Report {
+    let mut processes: Vec<_> = (0..count).map(|id| Process::new(id as Integer)).collect();
+
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for id in 0..count {
+            loop {
+                match processes[id].step(program, Interpretation::SendReceive) {
+                    Event::Blocked => break,
+                    Event::Terminated => {
+                        // Values sent to this process won't be received
+                        processes[id].inbox.clear();
+                        break;
+                    }
+                    Event::Sent(integer) => {
+                        let next = &mut processes[(id + 1) % count];
+                        if !next.is_terminated(program) {
+                            next.inbox.push_back(integer);
+                        }
+                    }
+                    _ => {}
+                }
+                progress = true;
+            }
+        }
+    }
+
+    let deadlock = processes
+        .iter()
+        .any(|process| !process.is_terminated(program));
+    Report {
+        processes,
+        deadlock,
+    }
+}
+
+/// State shared by the threads of `run_threads`
+struct Shared {
+    /// Number of processes waiting for a value
+    waiting: usize,
+    /// Number of processes not terminated
+    alive: usize,
+    terminated: Vec<bool>,
+    /// Number of values sent to a process still alive, and not received yet
+    in_flight: usize,
+    deadlock: bool,
+}
+
+/// Same as `run`, but with each process in its own thread, the values being sent over channels
+pub fn run_threads(program: &[Instruction], count: usize) -> Report {
+    let shared = Mutex::new(Shared {
+        waiting: 0,
+        alive: count,
+        terminated: vec![false; count],
+        in_flight: 0,
+        deadlock: false,
+    });
+    let condvar = Condvar::new();
+    let (senders, receivers): (Vec<Sender<Integer>>, Vec<Receiver<Integer>>) =
+        (0..count).map(|_| channel()).unzip();
+
+    let processes = thread::scope(|scope| {
+        let handles: Vec<_> = receivers
+            .into_iter()
+            .enumerate()
+            .map(|(id, receiver)| {
+                let next = (id + 1) % count;
+                let sender = senders[next].clone();
+                let (shared, condvar) = (&shared, &condvar);
+                scope.spawn(move || {
+                    let mut process = Process::new(id as Integer);
+                    loop {
+                        if process.is_waiting(program) {
+                            // Channels are only used with the lock held, so a value can't be
+                            // sent between the check and the wait
+                            let mut state = shared.lock().unwrap();
+                            state.waiting += 1;
+                            loop {
+                                if let Ok(integer) = receiver.try_recv() {
+                                    state.in_flight -= 1;
+                                    process.inbox.push_back(integer);
+                                    break;
+                                }
+                                if state.waiting == state.alive && state.in_flight == 0 {
+                                    state.deadlock = true;
+                                    condvar.notify_all();
+                                }
+                                if state.deadlock {
+                                    return process;
+                                }
+                                state = condvar.wait(state).unwrap();
+                            }
+                            state.waiting -= 1;
+                        }
+
+                        match process.step(program, Interpretation::SendReceive) {
+                            Event::Sent(integer) => {
+                                let mut state = shared.lock().unwrap();
+                                if !state.terminated[next] {
+                                    state.in_flight += 1;
+                                    sender.send(integer).unwrap();
+                                    condvar.notify_all();
+                                }
+                            }
+                            Event::Terminated => {
+                                let mut state = shared.lock().unwrap();
+                                state.terminated[id] = true;
+                                state.alive -= 1;
+                                // Values sent to this process won't be received
+                                while receiver.try_recv().is_ok() {
+                                    state.in_flight -= 1;
+                                }
+                                condvar.notify_all();
+                                return process;
+                            }
+                            _ => {}
+                        }
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    let deadlock = shared.into_inner().unwrap().deadlock;
+    Report {
+        processes,
+        deadlock,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use machine::parse;
+
+    #[test]
+    fn play_test() {
+        let program = parse(
+            "set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\njgz a -1\nset a 1\njgz a -2",
+        ).unwrap();
+        assert_eq!(play(&program), Some(4));
+        assert_eq!(play(&parse("snd 3\nrcv a").unwrap()), None);
+    }
+
+    #[test]
+    fn run_test() {
+        let program = parse("snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d").unwrap();
+        for report in &[run(&program, 2), run_threads(&program, 2)] {
+            assert!(report.deadlock);
+            assert_eq!(report.processes[1].sent, 3);
+            assert_eq!(report.processes[0].registers[&'c'], 1);
+            assert_eq!(report.processes[1].registers[&'c'], 0);
+        }
+
+        // The first process terminates, the second one waits for a value forever
+        let program = parse("jgz p 2\njgz 1 3\nrcv a\nrcv a").unwrap();
+        let report = run(&program, 2);
+        assert!(report.deadlock);
+        assert_eq!(run_threads(&program, 2), report);
+        assert!(report.processes[0].is_terminated(&program));
+
+        let report = run(&program, 1);
+        assert!(!report.deadlock);
+        assert_eq!(run_threads(&program, 1), report);
+    }
+
+    #[test]
+    fn input_test() {
+        let program = parse(include_str!("../tests/day18/input")).unwrap();
+        assert_eq!(play(&program), Some(7071));
+        let report = run(&program, 2);
+        assert_eq!(report.processes[1].sent, 8001);
+        assert_eq!(run_threads(&program, 2), report);
+        assert_eq!(run(&program, 3).processes.len(), 3);
+    }
+}
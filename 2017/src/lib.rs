@@ -1,5 +1,8 @@
 extern crate itertools;
 
+pub mod coprocessor;
+pub mod duet;
 pub mod knot;
+pub mod machine;
 
 // pub use knot;
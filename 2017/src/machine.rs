@@ -0,0 +1,164 @@
+//! Register machine of days 18 and 23. Both instruction sets are supported, `snd` and `rcv`
+//! being left to the caller since their meaning depends on how the program is run.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub type Integer = i64;
+pub type Register = char;
+pub type Registers = HashMap<Register, Integer>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    Register(Register),
+    Integer(Integer),
+}
+
+impl Value {
+    pub fn as_integer(&self, registers: &Registers) -> Integer {
+        match *self {
+            Value::Register(ref register) => registers.get(register).cloned().unwrap_or(0),
+            Value::Integer(integer) => integer,
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = String;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        match v.parse() {
+            Ok(integer) => Ok(Value::Integer(integer)),
+            Err(_) => Ok(Value::Register(register(v)?)),
+        }
+    }
+}
+
+fn register(v: &str) -> Result<Register, String> {
+    let mut chars = v.chars();
+    match (chars.next(), chars.next()) {
+        (Some(register), None) if register.is_ascii_lowercase() => Ok(register),
+        _ => Err(format!("Invalid register {:?}", v)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Snd(Value),
+    Set(Register, Value),
+    Add(Register, Value),
+    Sub(Register, Value),
+    Mul(Register, Value),
+    Mod(Register, Value),
+    Rcv(Register),
+    Jgz(Value, Value),
+    Jnz(Value, Value),
+}
+
+impl Instruction {
+    /// Execute the instruction and return the offset of the next one, or `None` for `snd` and
+    /// `rcv`
+    pub fn execute(&self, registers: &mut Registers) -> Option<Integer> {
+        match *self {
+            Instruction::Snd(_) | Instruction::Rcv(_) => return None,
+            Instruction::Set(register, value) => {
+                let integer = value.as_integer(registers);
+                registers.insert(register, integer);
+            }
+            Instruction::Add(register, value) => {
+                let integer = value.as_integer(registers);
+                *registers.entry(register).or_insert(0) += integer;
+            }
+            Instruction::Sub(register, value) => {
+                let integer = value.as_integer(registers);
+                *registers.entry(register).or_insert(0) -= integer;
+            }
+            Instruction::Mul(register, value) => {
+                let integer = value.as_integer(registers);
+                *registers.entry(register).or_insert(0) *= integer;
+            }
+            Instruction::Mod(register, value) => {
+                let integer = value.as_integer(registers);
+                *registers.entry(register).or_insert(0) %= integer;
+            }
+            Instruction::Jgz(condition, value) => {
+                if condition.as_integer(registers) > 0 {
+                    return Some(value.as_integer(registers));
+                }
+            }
+            Instruction::Jnz(condition, value) => {
+                if condition.as_integer(registers) != 0 {
+                    return Some(value.as_integer(registers));
+                }
+            }
+        }
+        Some(1)
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+    fn from_str(v: &str) -> Result<Self, Self::Err> {
+        let chunks: Vec<&str> = v.trim().split(' ').collect();
+
+        Ok(match chunks[..] {
+            ["snd", x] => Instruction::Snd(x.parse()?),
+            ["set", x, y] => Instruction::Set(register(x)?, y.parse()?),
+            ["add", x, y] => Instruction::Add(register(x)?, y.parse()?),
+            ["sub", x, y] => Instruction::Sub(register(x)?, y.parse()?),
+            ["mul", x, y] => Instruction::Mul(register(x)?, y.parse()?),
+            ["mod", x, y] => Instruction::Mod(register(x)?, y.parse()?),
+            ["rcv", x] => Instruction::Rcv(register(x)?),
+            ["jgz", x, y] => Instruction::Jgz(x.parse()?, y.parse()?),
+            ["jnz", x, y] => Instruction::Jnz(x.parse()?, y.parse()?),
+            _ => return Err(format!("Invalid instruction {:?}", v)),
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, String> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.parse())
+        .collect()
+}
+
+/// Instruction at `pointer`, or `None` if the program terminated
+pub fn fetch(program: &[Instruction], pointer: Integer) -> Option<Instruction> {
+    if pointer < 0 {
+        None
+    } else {
+        program.get(pointer as usize).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse("sub a -3\njnz a b\n").unwrap(),
+            vec![
+                Instruction::Sub('a', Value::Integer(-3)),
+                Instruction::Jnz(Value::Register('a'), Value::Register('b')),
+            ]
+        );
+        assert!(parse("sub ab 1").is_err());
+        assert!(parse("div a 1").is_err());
+    }
+
+    #[test]
+    fn execute_test() {
+        let mut registers = Registers::new();
+        assert_eq!(
+            Instruction::Sub('a', Value::Integer(-3)).execute(&mut registers),
+            Some(1)
+        );
+        assert_eq!(registers[&'a'], 3);
+        let jump = Instruction::Jnz(Value::Register('a'), Value::Integer(-2));
+        assert_eq!(jump.execute(&mut registers), Some(-2));
+        assert_eq!(Instruction::Rcv('a').execute(&mut registers), None);
+    }
+}
@@ -1,6 +1,3 @@
-#![feature(test)]
-extern crate test;
-
 /// Solution using a `VecDeque` to represent the board, inspired by
 /// [Frank de Jong solution](https://github.com/foo-jin/advent-of-code/blob/master/2018/day09/src/main.rs)
 /// This is slightly more efficient than my solution using a double linked list.
@@ -57,10 +54,14 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use aoc::bench::{self, Comparison, Stats};
 
-    #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| max_score(468, 71010 * 100))
+    /// Run with `cargo test --release --bin day09-vec_deque -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench() {
+        let stats = Stats::measure(10, || max_score(468, 71010 * 100));
+        let comparison = bench::check("2018-09-vec_deque", stats).unwrap();
+        assert!(!matches!(comparison, Comparison::Regression(_)));
     }
 }
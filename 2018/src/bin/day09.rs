@@ -1,6 +1,3 @@
-#![feature(test)]
-extern crate test;
-
 use regex::Regex;
 use std::io::{stdin, BufRead};
 
@@ -128,10 +125,14 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
-
-    #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| max_score(468, 71010 * 100))
+    use aoc::bench::{self, Comparison, Stats};
+
+    /// Run with `cargo test --release --bin day09 -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench() {
+        let stats = Stats::measure(10, || max_score(468, 71010 * 100));
+        let comparison = bench::check("2018-09", stats).unwrap();
+        assert!(!matches!(comparison, Comparison::Regression(_)));
     }
 }
@@ -1,6 +1,7 @@
-use itertools::Itertools;
-use std::collections::{HashMap, VecDeque};
+use aoc::cycle;
+use std::collections::HashMap;
 use std::io::{stdin, BufRead};
+use std::iter;
 
 fn parse_pots(iter: &mut impl Iterator<Item = char>) -> Vec<bool> {
     iter.take_while(|&ch| ch == '.' || ch == '#')
@@ -8,6 +9,43 @@ fn parse_pots(iter: &mut impl Iterator<Item = char>) -> Vec<bool> {
         .collect()
 }
 
+type Rules = HashMap<(bool, bool, bool, bool, bool), bool>;
+
+/// Pots, starting and ending with a plant, and the number of the first one
+type Pots = (i64, Vec<bool>);
+
+fn trim((offset, pots): Pots) -> Pots {
+    let first = pots.iter().position(|&b| b).unwrap_or(0);
+    let last = pots.iter().rposition(|&b| b).map_or(0, |last| last + 1);
+    (offset + first as i64, pots[first..last].to_vec())
+}
+
+fn next_generation(rules: &Rules, (offset, pots): &Pots) -> Pots {
+    // Pad with 4 empty pots on each side, so every pot that can get a plant has a full window
+    let padded: Vec<_> = iter::repeat_n(false, 4)
+        .chain(pots.iter().cloned())
+        .chain(iter::repeat_n(false, 4))
+        .collect();
+    let pots = padded
+        .windows(5)
+        .map(|bools| {
+            rules
+                .get(&(bools[0], bools[1], bools[2], bools[3], bools[4]))
+                .cloned()
+                .unwrap_or(false)
+        })
+        .collect();
+    trim((offset - 2, pots))
+}
+
+fn pots_sum((offset, pots): &Pots) -> i64 {
+    pots.iter()
+        .enumerate()
+        .filter(|&(_, &b)| b)
+        .map(|(index, _)| offset + index as i64)
+        .sum()
+}
+
 fn main() {
     let stdin = stdin();
     let mut lines = stdin.lock().lines().filter_map(|l| l.ok());
@@ -17,7 +55,7 @@ fn main() {
     let initial_state = parse_pots(&mut first_line.chars().skip(15));
 
     // Parse rules into a HashMap
-    let rules: HashMap<(bool, bool, bool, bool, bool), bool> = lines
+    let rules: Rules = lines
         .skip(1)
         .filter_map(|line| {
             let mut chars = line.chars();
@@ -33,82 +71,27 @@ fn main() {
         })
         .collect();
 
-    // The size to add when there is not enough space before the first filled plant or after the
-    // last one
-    let padding_size = 20;
+    let next = |state: &Pots| next_generation(&rules, state);
+    let initial = trim((0, initial_state));
 
-    // Start with the initial state
-    let mut state = initial_state;
-    // No padding yet
-    let mut padding_start = 0;
-    // Collect previous sums for Part 2
-    let mut previous_sums = VecDeque::new();
+    // Part 1: the sum at generation 20
+    let state = (0..20).fold(initial.clone(), |state, _| next(&state));
+    println!("Part 1: {}", pots_sum(&state));
 
-    for generation in 0.. {
-        // Compute the sum for this state
-        let sum = state
-            .iter()
-            .enumerate()
-            .map(|(index, &b)| {
-                if b {
-                    index as i64 - padding_start as i64
-                } else {
-                    0
-                }
-            })
-            .sum::<i64>();
-
-        if generation == 20 {
-            // Part 1: display the sum at generation 20
-            println!("Part 1: {}", sum);
-        }
-
-        // Callect the 5 previous sums
-        previous_sums.push_back(sum);
-        if previous_sums.len() > 5 {
-            // If all previous sums difference are equal, we expect that all further generation sum
-            // will increase by the same amount
-            let factor = previous_sums[1] - previous_sums[0];
-            if previous_sums
-                .iter()
-                .tuple_windows()
-                .map(|(a, b)| b - a)
-                .all(|f| f == factor)
-            {
-                // Compute the sum for the step 50_000_000_000
-                println!("Part 2: {}", sum + ((50_000_000_000 - generation) * factor));
-                break;
-            } else {
-                // Drop the oldest sum, so we keep the previous_sums length to 5
-                previous_sums.pop_front();
-            }
-        }
-
-        // Compute the next generation
-
-        // First, we pad the state with empty pots, at the beginning and the end, if there is less
-        // than 3 empty pots on each side.
-        let mut tmp_state = Vec::new();
-
-        if state.iter().position(|&b| b).unwrap() < 3 {
-            tmp_state.extend(std::iter::repeat(false).take(padding_size));
-            padding_start += padding_size;
-        }
-
-        tmp_state.extend(&state);
-
-        if state.len() - state.iter().rposition(|&b| b).unwrap() - 1 < 3 {
-            tmp_state.extend(std::iter::repeat(false).take(padding_size));
-        }
-
-        // Then we can iterate over each pots to compute the future state. Start with two empty
-        // pots, because our window algorith is 'eating' the first two.
-        state = vec![false; 2];
-        state.extend(tmp_state.windows(5).map(|bools| {
-            rules
-                .get(&(bools[0], bools[1], bools[2], bools[3], bools[4]))
-                .cloned()
-                .unwrap_or(false)
-        }));
-    }
+    // Part 2: the plants end up moving as a fixed pattern, so find when the pattern repeats,
+    // regardless of its position, and how far it moves each time
+    let generations = 50_000_000_000;
+    let cycle = cycle::find_cycle_by_key(initial.clone(), next, |(_, pots)| pots.clone());
+    let start = (0..cycle.start).fold(initial, |state, _| next(&state));
+    let shift = (0..cycle.length)
+        .fold(start.clone(), |state, _| next(&state))
+        .0
+        - start.0;
+    let repetitions = (generations - cycle.start) / cycle.length;
+    let (offset, pots) =
+        (0..cycle.equivalent_step(generations) - cycle.start).fold(start, |state, _| next(&state));
+    println!(
+        "Part 2: {}",
+        pots_sum(&(offset + repetitions as i64 * shift, pots))
+    );
 }
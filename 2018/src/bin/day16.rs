@@ -1,82 +1,9 @@
+use advent_of_code_2018::elfcode::inference::{self, Sample};
+use advent_of_code_2018::elfcode::{Machine, Program, INSTRUCTIONS};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::io::{stdin, BufRead};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Instructions {
-    Addr,
-    Addi,
-    Mulr,
-    Muli,
-    Banr,
-    Bani,
-    Borr,
-    Bori,
-    Setr,
-    Seti,
-    Gtir,
-    Gtri,
-    Gtrr,
-    Eqir,
-    Eqri,
-    Eqrr,
-}
-
-static INSTRUCTIONS: [Instructions; 16] = [
-    Instructions::Addr,
-    Instructions::Addi,
-    Instructions::Mulr,
-    Instructions::Muli,
-    Instructions::Banr,
-    Instructions::Bani,
-    Instructions::Borr,
-    Instructions::Bori,
-    Instructions::Setr,
-    Instructions::Seti,
-    Instructions::Gtir,
-    Instructions::Gtri,
-    Instructions::Gtrr,
-    Instructions::Eqir,
-    Instructions::Eqri,
-    Instructions::Eqrr,
-];
-
-impl Instructions {
-    fn apply(self, (a, b, c): (usize, usize, usize), registers: &mut [usize]) {
-        match self {
-            Instructions::Addr => registers[c] = registers[a] + registers[b],
-            Instructions::Addi => registers[c] = registers[a] + b,
-            Instructions::Mulr => registers[c] = registers[a] * registers[b],
-            Instructions::Muli => registers[c] = registers[a] * b,
-            Instructions::Banr => registers[c] = registers[a] & registers[b],
-            Instructions::Bani => registers[c] = registers[a] & b,
-            Instructions::Borr => registers[c] = registers[a] | registers[b],
-            Instructions::Bori => registers[c] = registers[a] | b,
-            Instructions::Setr => registers[c] = registers[a],
-            Instructions::Seti => registers[c] = a,
-            Instructions::Gtir => registers[c] = if a > registers[b] { 1 } else { 0 },
-            Instructions::Gtri => registers[c] = if registers[a] > b { 1 } else { 0 },
-            Instructions::Gtrr => registers[c] = if registers[a] > registers[b] { 1 } else { 0 },
-            Instructions::Eqir => registers[c] = if a == registers[b] { 1 } else { 0 },
-            Instructions::Eqri => registers[c] = if registers[a] == b { 1 } else { 0 },
-            Instructions::Eqrr => registers[c] = if registers[a] == registers[b] { 1 } else { 0 },
-        }
-    }
-
-    fn test_sample(self, sample: &Sample) -> bool {
-        let mut registers = sample.before.clone();
-        self.apply(sample.line.parameters, &mut registers);
-        registers == sample.after
-    }
-}
-
-#[derive(Debug)]
-struct Sample {
-    before: Vec<usize>,
-    line: ProgramLine,
-    after: Vec<usize>,
-}
-
 #[derive(Debug)]
 struct ProgramLine {
     opcode: usize,
@@ -84,16 +11,16 @@ struct ProgramLine {
 }
 
 impl std::str::FromStr for ProgramLine {
-    type Err = ();
+    type Err = String;
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let line = parse_numbers(&line);
-        if line.len() == 4 {
+        let numbers = parse_numbers(line);
+        if numbers.len() == 4 {
             Ok(Self {
-                opcode: line[0],
-                parameters: (line[1], line[2], line[3]),
+                opcode: numbers[0],
+                parameters: (numbers[1], numbers[2], numbers[3]),
             })
         } else {
-            Err(())
+            Err(format!("Invalid program line {:?}", line))
         }
     }
 }
@@ -121,14 +48,17 @@ fn main() {
                 before = Some(parse_numbers(&line));
             } else if line.starts_with("After:") {
                 // Everything is collected to build the sample, push it
+                let program_line: ProgramLine = program_line.take().unwrap();
                 samples.push(Sample {
                     before: before.take().unwrap(),
+                    opcode: program_line.opcode,
+                    parameters: program_line.parameters,
                     after: parse_numbers(&line),
-                    line: program_line.take().unwrap(),
                 });
             }
-            // Try to parse the program line
-            else if let Ok(pl) = line.parse::<ProgramLine>() {
+            // Parse the program line
+            else if !line.trim().is_empty() {
+                let pl = line.parse::<ProgramLine>().unwrap();
                 // If we are building a Sample, store the program line for future usage
                 if before.is_some() {
                     program_line = Some(pl);
@@ -148,7 +78,7 @@ fn main() {
             .filter(|sample| {
                 let matching_instructions = INSTRUCTIONS
                     .iter()
-                    .filter(|instruction| instruction.test_sample(&sample))
+                    .filter(|instruction| sample.matches(*instruction))
                     .count();
                 matching_instructions >= 3
             })
@@ -159,30 +89,26 @@ fn main() {
 
     // Part 2
     {
-        let mut instruction_indexes: Vec<Instructions> = vec![Instructions::Addr; 16];
-        let mut unknown_instructions: Vec<_> = INSTRUCTIONS.to_vec();
-
-        // While there is unknown_instructions, try to determine instruction opcodes
-        while !unknown_instructions.is_empty() {
-            for sample in &samples {
-                let matching_instructions: Vec<_> = unknown_instructions
-                    .iter()
-                    .filter(|&instruction| instruction.test_sample(sample))
-                    .cloned()
-                    .collect();
-
-                if matching_instructions.len() == 1 {
-                    instruction_indexes[sample.line.opcode] = matching_instructions[0];
-                    unknown_instructions.retain(|&i| i != matching_instructions[0]);
-                }
-            }
-        }
-
-        // Execute the program
-        let mut registers = vec![0; 4];
-        for program_line in &test_program {
-            instruction_indexes[program_line.opcode].apply(program_line.parameters, &mut registers);
+        let table =
+            inference::infer(&INSTRUCTIONS, &samples).unwrap_or_else(|error| panic!("{}", error));
+
+        // Translate the test program, which can be printed in the format of days 19 and 21
+        let program = Program {
+            ip: None,
+            lines: test_program
+                .iter()
+                .map(|program_line| {
+                    table
+                        .program_line(program_line.opcode, program_line.parameters)
+                        .unwrap()
+                })
+                .collect(),
+        };
+        if std::env::args().any(|arg| arg == "--program") {
+            eprint!("{}", program);
         }
-        println!("Part 2: {}", registers[0]);
+        let mut machine = Machine::new(program, 4);
+        machine.run();
+        println!("Part 2: {}", machine.registers[0]);
     }
 }
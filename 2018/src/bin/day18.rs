@@ -1,8 +1,8 @@
+use aoc::cycle;
 use itertools::iproduct;
-use std::collections::HashMap;
 use std::io::{stdin, BufRead};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 enum Acre {
     Open,
     Trees,
@@ -79,7 +79,7 @@ fn grid_count(grid: &[Vec<Acre>]) -> (usize, usize) {
 }
 
 fn main() {
-    let mut grid: Vec<Vec<_>> = stdin()
+    let grid: Vec<Vec<_>> = stdin()
         .lock()
         .lines()
         .filter_map(|l| {
@@ -96,34 +96,14 @@ fn main() {
         })
         .collect();
 
-    let mut map = HashMap::new();
+    let (trees_count, lumberyard_count) =
+        grid_count(&cycle::nth_state(grid.clone(), |grid| grid_next(grid), 10));
+    println!("Part 1: {}", trees_count * lumberyard_count);
 
-    for minute in 0.. {
-        // grid_print(&grid);
-        let (trees_count, lumberyard_count) = grid_count(&grid);
-
-        if minute == 10 {
-            println!("Part 1: {}", trees_count * lumberyard_count);
-        }
-
-        // Collect counts into a map, to try to find a recurring period
-        let entries = map
-            .entry((trees_count, lumberyard_count))
-            .or_insert_with(Vec::new);
-        entries.push(minute);
-
-        // If a given count tuple is repeating 5 times, we consider it as a period
-        if entries.len() == 5 {
-            let period = entries[4] - entries[3];
-            let remaining = 1_000_000_000 - minute;
-            for _ in 0..remaining % period {
-                grid = grid_next(&grid);
-            }
-            let (trees_count, lumberyard_count) = grid_count(&grid);
-            println!("Part 2: {}", trees_count * lumberyard_count);
-            break;
-        }
-
-        grid = grid_next(&grid);
-    }
+    let (trees_count, lumberyard_count) = grid_count(&cycle::nth_state(
+        grid,
+        |grid| grid_next(grid),
+        1_000_000_000,
+    ));
+    println!("Part 2: {}", trees_count * lumberyard_count);
 }
@@ -1,3 +1,5 @@
+use aoc::union_find::UnionFind;
+use itertools::Itertools;
 use std::io::{stdin, BufRead};
 
 type Coords = (isize, isize, isize, isize);
@@ -7,8 +9,7 @@ fn distance(a: &Coords, b: &Coords) -> isize {
 }
 
 fn main() {
-    let mut constellations: Vec<Vec<Coords>> = Vec::new();
-    stdin()
+    let points: Vec<Coords> = stdin()
         .lock()
         .lines()
         .filter_map(|l| l.ok())
@@ -21,30 +22,14 @@ fn main() {
                 parts.next()?.parse().ok()?,
             ))
         })
-        .for_each(|coords| {
-            #[allow(clippy::filter_map)]
-            let indexes: Vec<_> = constellations
-                .iter()
-                .enumerate()
-                .filter(|&(_, constellation)| {
-                    constellation
-                        .iter()
-                        .any(|other| distance(&coords, other) <= 3)
-                })
-                .map(|(index, _)| index)
-                .collect();
+        .collect();
 
-            if indexes.is_empty() {
-                constellations.push(vec![coords]);
-            } else {
-                let target = indexes[0];
-                constellations[target].push(coords);
-                for index in indexes.into_iter().skip(1).rev() {
-                    let a = constellations.remove(index);
-                    constellations[target].extend(a);
-                }
-            }
-        });
+    let mut constellations = UnionFind::new(points.len());
+    for (a, b) in (0..points.len()).tuple_combinations() {
+        if distance(&points[a], &points[b]) <= 3 {
+            constellations.union(a, b);
+        }
+    }
 
-    println!("{}", constellations.len());
+    println!("{}", constellations.component_count());
 }
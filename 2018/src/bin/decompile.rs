@@ -0,0 +1,16 @@
+// Print an Elfcode program (days 19 and 21) as pseudocode, followed by its constants
+use advent_of_code_2018::elfcode::{decompiler, Program};
+use std::io::{stdin, Read};
+
+fn main() {
+    let mut input = String::new();
+    stdin().read_to_string(&mut input).unwrap();
+    let program: Program = input.parse().unwrap_or_else(|error| panic!("{}", error));
+
+    let decompiled = decompiler::decompile(&program);
+    print!("{}", decompiled);
+    println!();
+    for (line, value) in decompiled.constants() {
+        println!("// line {}: {}", line, value);
+    }
+}
@@ -0,0 +1,347 @@
+//! The device language of days 16, 19 and 21
+
+pub mod decompiler;
+pub mod halting;
+pub mod inference;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instructions {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+pub static INSTRUCTIONS: [Instructions; 16] = [
+    Instructions::Addr,
+    Instructions::Addi,
+    Instructions::Mulr,
+    Instructions::Muli,
+    Instructions::Banr,
+    Instructions::Bani,
+    Instructions::Borr,
+    Instructions::Bori,
+    Instructions::Setr,
+    Instructions::Seti,
+    Instructions::Gtir,
+    Instructions::Gtri,
+    Instructions::Gtrr,
+    Instructions::Eqir,
+    Instructions::Eqri,
+    Instructions::Eqrr,
+];
+
+impl Instructions {
+    pub fn apply(self, (a, b, c): (usize, usize, usize), registers: &mut [usize]) {
+        registers[c] = match self {
+            Instructions::Addr => registers[a] + registers[b],
+            Instructions::Addi => registers[a] + b,
+            Instructions::Mulr => registers[a] * registers[b],
+            Instructions::Muli => registers[a] * b,
+            Instructions::Banr => registers[a] & registers[b],
+            Instructions::Bani => registers[a] & b,
+            Instructions::Borr => registers[a] | registers[b],
+            Instructions::Bori => registers[a] | b,
+            Instructions::Setr => registers[a],
+            Instructions::Seti => a,
+            Instructions::Gtir => usize::from(a > registers[b]),
+            Instructions::Gtri => usize::from(registers[a] > b),
+            Instructions::Gtrr => usize::from(registers[a] > registers[b]),
+            Instructions::Eqir => usize::from(a == registers[b]),
+            Instructions::Eqri => usize::from(registers[a] == b),
+            Instructions::Eqrr => usize::from(registers[a] == registers[b]),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Instructions::Addr => "addr",
+            Instructions::Addi => "addi",
+            Instructions::Mulr => "mulr",
+            Instructions::Muli => "muli",
+            Instructions::Banr => "banr",
+            Instructions::Bani => "bani",
+            Instructions::Borr => "borr",
+            Instructions::Bori => "bori",
+            Instructions::Setr => "setr",
+            Instructions::Seti => "seti",
+            Instructions::Gtir => "gtir",
+            Instructions::Gtri => "gtri",
+            Instructions::Gtrr => "gtrr",
+            Instructions::Eqir => "eqir",
+            Instructions::Eqri => "eqri",
+            Instructions::Eqrr => "eqrr",
+        }
+    }
+}
+
+impl fmt::Display for Instructions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Instructions {
+    type Err = ParseError;
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        INSTRUCTIONS
+            .iter()
+            .find(|instruction| instruction.name() == name)
+            .cloned()
+            .ok_or_else(|| ParseError::new(1, name, "unknown instruction"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, starting at 1
+    pub line: usize,
+    pub text: String,
+    pub reason: &'static str,
+}
+
+impl ParseError {
+    fn new(line: usize, text: &str, reason: &'static str) -> Self {
+        Self {
+            line,
+            text: text.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {} in {:?}", self.line, self.reason, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramLine {
+    pub instruction: Instructions,
+    pub parameters: (usize, usize, usize),
+}
+
+impl fmt::Display for ProgramLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b, c) = self.parameters;
+        write!(f, "{} {} {} {}", self.instruction, a, b, c)
+    }
+}
+
+impl FromStr for ProgramLine {
+    type Err = ParseError;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^([a-z]{4}) (\d+) (\d+) (\d+)$").unwrap();
+        }
+        let caps = RE
+            .captures(line.trim())
+            .ok_or_else(|| ParseError::new(1, line, "expected an instruction and 3 parameters"))?;
+        let parameter = |index| {
+            caps.get(index)
+                .unwrap()
+                .as_str()
+                .parse()
+                .map_err(|_| ParseError::new(1, line, "invalid parameter"))
+        };
+        Ok(Self {
+            instruction: caps
+                .get(1)
+                .unwrap()
+                .as_str()
+                .parse()
+                .map_err(|_| ParseError::new(1, line, "unknown instruction"))?,
+            parameters: (parameter(2)?, parameter(3)?, parameter(4)?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    /// Register bound to the instruction pointer by the `#ip` directive
+    pub ip: Option<usize>,
+    pub lines: Vec<ProgramLine>,
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ip) = self.ip {
+            writeln!(f, "#ip {}", ip)?;
+        }
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Program {
+    type Err = ParseError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut program = Program {
+            ip: None,
+            lines: Vec::new(),
+        };
+        for (index, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(register) = line.trim().strip_prefix("#ip ") {
+                program.ip = Some(
+                    register
+                        .parse()
+                        .map_err(|_| ParseError::new(index + 1, line, "invalid #ip directive"))?,
+                );
+            } else {
+                program
+                    .lines
+                    .push(line.parse().map_err(|error: ParseError| ParseError {
+                        line: index + 1,
+                        ..error
+                    })?);
+            }
+        }
+        Ok(program)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine {
+    pub program: Program,
+    pub registers: Vec<usize>,
+    /// Index of the next line to execute
+    pub pointer: usize,
+    /// Number of instructions executed
+    pub steps: u64,
+    /// Number of times each line has been executed
+    pub line_counts: Vec<u64>,
+}
+
+impl Machine {
+    pub fn new(program: Program, register_count: usize) -> Self {
+        if let Some(ip) = program.ip {
+            assert!(ip < register_count, "#ip {} is not a register", ip);
+        }
+        Self {
+            line_counts: vec![0; program.lines.len()],
+            program,
+            registers: vec![0; register_count],
+            pointer: 0,
+            steps: 0,
+        }
+    }
+
+    /// Next line to execute, or `None` if the program halted
+    pub fn current_line(&self) -> Option<&ProgramLine> {
+        self.program.lines.get(self.pointer)
+    }
+
+    /// Execute the next line, and return `false` if the program halted
+    pub fn step(&mut self) -> bool {
+        let line = match self.current_line() {
+            Some(line) => *line,
+            None => return false,
+        };
+        if let Some(ip) = self.program.ip {
+            self.registers[ip] = self.pointer;
+        }
+        line.instruction.apply(line.parameters, &mut self.registers);
+        self.line_counts[self.pointer] += 1;
+        self.steps += 1;
+        if let Some(ip) = self.program.ip {
+            self.pointer = self.registers[ip];
+        }
+        self.pointer += 1;
+        true
+    }
+
+    /// Run until the program halts
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+
+    /// Run until `predicate` is true before executing a line, and return `false` if the program
+    /// halted first
+    pub fn run_until(&mut self, mut predicate: impl FnMut(&Machine) -> bool) -> bool {
+        loop {
+            if self.current_line().is_none() {
+                return false;
+            }
+            if predicate(self) {
+                return true;
+            }
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+    #[test]
+    fn test_parse() {
+        let program: Program = EXAMPLE.parse().unwrap();
+        assert_eq!(program.ip, Some(0));
+        assert_eq!(program.lines.len(), 7);
+        assert_eq!(program.lines[3].to_string(), "addr 1 2 3");
+        assert_eq!(program.to_string(), EXAMPLE);
+
+        let error = "#ip 0\nseti 5 0 1\nfoo 1 2 3\n"
+            .parse::<Program>()
+            .unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(
+            "#ip 0\nseti 5 0 1\nmulx 1 2 3\n"
+                .parse::<Program>()
+                .unwrap_err()
+                .reason,
+            "unknown instruction"
+        );
+        assert_eq!("#ip x".parse::<Program>().unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut machine = Machine::new(EXAMPLE.parse().unwrap(), 6);
+        machine.run();
+        assert_eq!(machine.registers, [6, 5, 6, 0, 0, 9]);
+        assert_eq!(machine.steps, 5);
+        assert_eq!(machine.line_counts, [1, 1, 1, 0, 1, 0, 1]);
+
+        let mut machine = Machine::new(EXAMPLE.parse().unwrap(), 6);
+        assert!(machine.run_until(|machine| machine.pointer == 4));
+        assert_eq!(machine.registers[1], 5);
+        assert!(!machine.run_until(|_| false));
+    }
+}
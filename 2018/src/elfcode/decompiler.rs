@@ -0,0 +1,598 @@
+//! Turn an Elfcode program into structured pseudocode. Reads of the instruction pointer register
+//! are replaced by the line number, and writes to it become jumps, which are structured into loops
+//! and conditionals when possible.
+
+use super::{Instructions, Program, ProgramLine};
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(usize),
+    Const(usize),
+    /// Read of the instruction pointer register, which holds the line number
+    Line(usize),
+}
+
+impl Operand {
+    fn value(self) -> Option<usize> {
+        match self {
+            Operand::Register(_) => None,
+            Operand::Const(value) | Operand::Line(value) => Some(value),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Register(register) => write!(f, "r{}", register),
+            Operand::Const(value) | Operand::Line(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expr {
+    Value(Operand),
+    Binary(Operand, &'static str, Operand),
+}
+
+impl Expr {
+    fn new(line: &ProgramLine, ip: Option<usize>, index: usize) -> Self {
+        let (a, b, _) = line.parameters;
+        let register = |register| {
+            if Some(register) == ip {
+                Operand::Line(index)
+            } else {
+                Operand::Register(register)
+            }
+        };
+        let (ra, rb, ia, ib) = (
+            register(a),
+            register(b),
+            Operand::Const(a),
+            Operand::Const(b),
+        );
+        match line.instruction {
+            Instructions::Addr => Expr::Binary(ra, "+", rb),
+            Instructions::Addi => Expr::Binary(ra, "+", ib),
+            Instructions::Mulr => Expr::Binary(ra, "*", rb),
+            Instructions::Muli => Expr::Binary(ra, "*", ib),
+            Instructions::Banr => Expr::Binary(ra, "&", rb),
+            Instructions::Bani => Expr::Binary(ra, "&", ib),
+            Instructions::Borr => Expr::Binary(ra, "|", rb),
+            Instructions::Bori => Expr::Binary(ra, "|", ib),
+            Instructions::Setr => Expr::Value(ra),
+            Instructions::Seti => Expr::Value(ia),
+            Instructions::Gtir => Expr::Binary(ia, ">", rb),
+            Instructions::Gtri => Expr::Binary(ra, ">", ib),
+            Instructions::Gtrr => Expr::Binary(ra, ">", rb),
+            Instructions::Eqir => Expr::Binary(ia, "==", rb),
+            Instructions::Eqri => Expr::Binary(ra, "==", ib),
+            Instructions::Eqrr => Expr::Binary(ra, "==", rb),
+        }
+    }
+
+    /// Value of an expression that doesn't read any register
+    fn eval(self) -> Option<usize> {
+        match self {
+            Expr::Value(operand) => operand.value(),
+            Expr::Binary(left, op, right) => {
+                let (left, right) = (left.value()?, right.value()?);
+                Some(match op {
+                    "+" => left + right,
+                    "*" => left * right,
+                    "&" => left & right,
+                    "|" => left | right,
+                    ">" => usize::from(left > right),
+                    _ => usize::from(left == right),
+                })
+            }
+        }
+    }
+
+    fn condition(self) -> Option<Condition> {
+        match self {
+            Expr::Binary(left, op, right) if op == ">" || op == "==" => {
+                Some(Condition { left, op, right })
+            }
+            _ => None,
+        }
+    }
+
+    fn operands(self) -> Vec<Operand> {
+        match self {
+            Expr::Value(operand) => vec![operand],
+            Expr::Binary(left, _, right) => vec![left, right],
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Value(operand) => write!(f, "{}", operand),
+            Expr::Binary(left, op, right) => write!(f, "{} {} {}", left, op, right),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Condition {
+    pub left: Operand,
+    pub op: &'static str,
+    pub right: Operand,
+}
+
+impl Condition {
+    pub fn negate(self) -> Self {
+        let op = match self.op {
+            ">" => "<=",
+            "<=" => ">",
+            "==" => "!=",
+            _ => "==",
+        };
+        Condition { op, ..self }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.op, self.right)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statement {
+    Assign(usize, Expr),
+    Goto(usize),
+    /// Jump to a line when the condition is true. A line after the end of the program halts.
+    If(Condition, usize),
+    /// Jump to the line following the value of the expression
+    ComputedGoto(Expr),
+    Halt,
+    /// Part of the conditional jump of a previous line
+    Folded,
+}
+
+impl Statement {
+    fn target(self) -> Option<usize> {
+        match self {
+            Statement::Goto(target) | Statement::If(_, target) => Some(target),
+            _ => None,
+        }
+    }
+}
+
+/// One statement per line of the program. A comparison followed by a jump over the next line,
+/// like `eqrr 1 2 3`, `addr 3 ip ip`, is folded into a single conditional jump. The register
+/// holding the comparison result is then considered as a scratch register, and isn't assigned.
+pub fn statements(program: &Program) -> Vec<Statement> {
+    let len = program.lines.len();
+    let mut statements: Vec<_> = program
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let expr = Expr::new(line, program.ip, index);
+            let register = line.parameters.2;
+            if Some(register) != program.ip {
+                Statement::Assign(register, expr)
+            } else {
+                match expr.eval() {
+                    Some(value) if value + 1 < len => Statement::Goto(value + 1),
+                    Some(_) => Statement::Halt,
+                    None => Statement::ComputedGoto(expr),
+                }
+            }
+        })
+        .collect();
+
+    let targets: BTreeSet<_> = statements.iter().filter_map(|s| s.target()).collect();
+    for index in 1..len {
+        let register = match statements[index] {
+            Statement::ComputedGoto(Expr::Binary(Operand::Line(_), "+", Operand::Register(r)))
+            | Statement::ComputedGoto(Expr::Binary(Operand::Register(r), "+", Operand::Line(_))) => {
+                r
+            }
+            _ => continue,
+        };
+        let condition = match statements[index - 1] {
+            Statement::Assign(r, expr) if r == register => match expr.condition() {
+                Some(condition) => condition,
+                None => continue,
+            },
+            _ => continue,
+        };
+        if targets.contains(&index) {
+            continue;
+        }
+        statements[index - 1] = Statement::If(condition, index + 2);
+        statements[index] = Statement::Folded;
+
+        // An unconditional jump follows, so jump to it when the condition is false
+        if index + 1 < len && !targets.contains(&(index + 1)) {
+            let target = match statements[index + 1] {
+                Statement::Goto(target) => target,
+                Statement::Halt => len,
+                _ => continue,
+            };
+            statements[index - 1] = Statement::If(condition.negate(), target);
+            statements[index + 1] = Statement::Folded;
+        }
+    }
+    statements
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Statement(usize, Statement),
+    If {
+        line: usize,
+        condition: Condition,
+        body: Vec<Node>,
+    },
+    DoWhile {
+        line: usize,
+        body: Vec<Node>,
+        condition: Condition,
+        /// Line following the loop
+        end: usize,
+    },
+    Loop {
+        line: usize,
+        body: Vec<Node>,
+        end: usize,
+    },
+}
+
+impl Node {
+    fn line(&self) -> usize {
+        match self {
+            Node::Statement(line, _)
+            | Node::If { line, .. }
+            | Node::DoWhile { line, .. }
+            | Node::Loop { line, .. } => *line,
+        }
+    }
+}
+
+/// Whether a line outside of `start..end` jumps inside it, other than on `start` when
+/// `allow_start` is set
+fn is_entered(statements: &[Statement], start: usize, end: usize, allow_start: bool) -> bool {
+    statements.iter().enumerate().any(|(index, statement)| {
+        !(start..end).contains(&index)
+            && statement.target().is_some_and(|target| {
+                (start..end).contains(&target) && !(allow_start && target == start)
+            })
+    })
+}
+
+/// Whether a line of `start..end` jumps outside of it, other than to `end` or to halt
+fn is_exited(statements: &[Statement], start: usize, end: usize) -> bool {
+    statements[start..end]
+        .iter()
+        .any(|statement| match statement {
+            Statement::ComputedGoto(_) => true,
+            _ => statement.target().is_some_and(|target| {
+                target < start || (target > end && target < statements.len())
+            }),
+        })
+}
+
+fn structure(statements: &[Statement], start: usize, end: usize) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut index = start;
+    while index < end {
+        // Loop jumping back to this line
+        let last = (index..end).rev().find(|&last| {
+            statements[last].target() == Some(index)
+                && !is_entered(statements, index, last + 1, true)
+                && !is_exited(statements, index, last + 1)
+        });
+        if let Some(last) = last {
+            let body = structure(statements, index, last);
+            nodes.push(match statements[last] {
+                Statement::If(condition, _) => Node::DoWhile {
+                    line: index,
+                    body,
+                    condition,
+                    end: last + 1,
+                },
+                _ => Node::Loop {
+                    line: index,
+                    body,
+                    end: last + 1,
+                },
+            });
+            index = last + 1;
+            continue;
+        }
+
+        // Jump over the next lines
+        if let Statement::If(condition, target) = statements[index] {
+            if target > index + 1
+                && target <= end
+                && !is_entered(statements, index + 1, target, false)
+            {
+                if !is_exited(statements, index + 1, target) {
+                    nodes.push(Node::If {
+                        line: index,
+                        condition: condition.negate(),
+                        body: structure(statements, index + 1, target),
+                    });
+                    index = target;
+                    continue;
+                }
+                // The lines jumped over are a single jump: use it when the condition is false
+                let jumps: Vec<_> = statements[index + 1..target]
+                    .iter()
+                    .filter(|statement| **statement != Statement::Folded)
+                    .collect();
+                let jump = match jumps[..] {
+                    [Statement::Goto(target)] => Some(*target),
+                    [Statement::Halt] => Some(statements.len()),
+                    _ => None,
+                };
+                if let Some(jump) = jump {
+                    nodes.push(Node::Statement(
+                        index,
+                        Statement::If(condition.negate(), jump),
+                    ));
+                    index = target;
+                    continue;
+                }
+            }
+        }
+
+        if statements[index] != Statement::Folded {
+            nodes.push(Node::Statement(index, statements[index]));
+        }
+        index += 1;
+    }
+    nodes
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decompiled {
+    pub ip: Option<usize>,
+    pub statements: Vec<Statement>,
+    pub nodes: Vec<Node>,
+}
+
+pub fn decompile(program: &Program) -> Decompiled {
+    let statements = statements(program);
+    let nodes = structure(&statements, 0, statements.len());
+    Decompiled {
+        ip: program.ip,
+        statements,
+        nodes,
+    }
+}
+
+impl Decompiled {
+    /// Immediate values of the program with their line, excluding jumps
+    pub fn constants(&self) -> Vec<(usize, usize)> {
+        let mut constants = Vec::new();
+        for (line, statement) in self.statements.iter().enumerate() {
+            let operands = match *statement {
+                Statement::Assign(_, expr) | Statement::ComputedGoto(expr) => expr.operands(),
+                Statement::If(condition, _) => vec![condition.left, condition.right],
+                _ => continue,
+            };
+            for operand in operands {
+                if let Operand::Const(value) = operand {
+                    constants.push((line, value));
+                }
+            }
+        }
+        constants
+    }
+
+    fn jump(&self, target: usize, end: Option<usize>, labels: &mut BTreeSet<usize>) -> String {
+        if target >= self.statements.len() {
+            "halt".to_string()
+        } else if Some(target) == end {
+            "break".to_string()
+        } else {
+            labels.insert(target);
+            format!("goto L{}", target)
+        }
+    }
+
+    /// Write nodes indented by `depth`, with the labels of `labels`, and collect the labels that
+    /// are jumped to in `used`. `end` is the line following the innermost loop.
+    fn write_nodes(
+        &self,
+        out: &mut String,
+        nodes: &[Node],
+        depth: usize,
+        end: Option<usize>,
+        labels: &mut BTreeSet<usize>,
+        used: &mut BTreeSet<usize>,
+    ) {
+        let indent = "    ".repeat(depth);
+        for node in nodes {
+            if labels.remove(&node.line()) {
+                writeln!(out, "L{}:", node.line()).unwrap();
+            }
+            match node {
+                Node::Statement(_, statement) => {
+                    let text = match *statement {
+                        Statement::Assign(
+                            register,
+                            Expr::Binary(Operand::Register(left), op, right),
+                        ) if left == register && ["+", "*", "&", "|"].contains(&op) => {
+                            format!("r{} {}= {}", register, op, right)
+                        }
+                        // These operators are commutative
+                        Statement::Assign(
+                            register,
+                            Expr::Binary(left, op, Operand::Register(right)),
+                        ) if right == register && ["+", "*", "&", "|"].contains(&op) => {
+                            format!("r{} {}= {}", register, op, left)
+                        }
+                        Statement::Assign(register, expr) => format!("r{} = {}", register, expr),
+                        Statement::Goto(target) => self.jump(target, end, used),
+                        Statement::If(condition, target) => {
+                            format!("if {} {}", condition, self.jump(target, end, used))
+                        }
+                        Statement::ComputedGoto(Expr::Binary(Operand::Line(line), "+", right))
+                        | Statement::ComputedGoto(Expr::Binary(right, "+", Operand::Line(line))) => {
+                            // The offset is unknown, any following line can be jumped to
+                            used.extend(line + 1..self.statements.len());
+                            format!("goto L({} + {})", line + 1, right)
+                        }
+                        Statement::ComputedGoto(ref expr) => {
+                            used.extend(0..self.statements.len());
+                            format!("goto L({} + 1)", expr)
+                        }
+                        Statement::Halt => "halt".to_string(),
+                        Statement::Folded => continue,
+                    };
+                    writeln!(out, "{}    {}", indent, text).unwrap();
+                }
+                Node::If {
+                    condition, body, ..
+                } => {
+                    writeln!(out, "{}    if {} {{", indent, condition).unwrap();
+                    self.write_nodes(out, body, depth + 1, end, labels, used);
+                    writeln!(out, "{}    }}", indent).unwrap();
+                }
+                Node::DoWhile {
+                    body,
+                    condition,
+                    end,
+                    ..
+                } => {
+                    writeln!(out, "{}    do {{", indent).unwrap();
+                    self.write_nodes(out, body, depth + 1, Some(*end), labels, used);
+                    writeln!(out, "{}    }} while {}", indent, condition).unwrap();
+                }
+                Node::Loop { body, end, .. } => {
+                    writeln!(out, "{}    loop {{", indent).unwrap();
+                    self.write_nodes(out, body, depth + 1, Some(*end), labels, used);
+                    writeln!(out, "{}    }}", indent).unwrap();
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Decompiled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Write once to know which labels are used
+        let mut labels = BTreeSet::new();
+        self.write_nodes(
+            &mut String::new(),
+            &self.nodes,
+            0,
+            None,
+            &mut BTreeSet::new(),
+            &mut labels,
+        );
+
+        let mut out = String::new();
+        if let Some(ip) = self.ip {
+            writeln!(out, "// r{} is the instruction pointer", ip).unwrap();
+        }
+        self.write_nodes(
+            &mut out,
+            &self.nodes,
+            0,
+            None,
+            &mut labels,
+            &mut BTreeSet::new(),
+        );
+        write!(f, "{}", out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statements() {
+        let program: Program = "#ip 3\neqrr 1 2 4\naddr 4 3 3\nseti 3 0 3\naddi 1 1 1\nmulr 3 3 3"
+            .parse()
+            .unwrap();
+        let condition = Condition {
+            left: Operand::Register(1),
+            op: "==",
+            right: Operand::Register(2),
+        };
+        assert_eq!(
+            statements(&program),
+            [
+                Statement::If(condition.negate(), 4),
+                Statement::Folded,
+                Statement::Folded,
+                Statement::Assign(
+                    1,
+                    Expr::Binary(Operand::Register(1), "+", Operand::Const(1))
+                ),
+                Statement::Halt,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decompile() {
+        let program: Program = include_str!("../../tests/day21/input").parse().unwrap();
+        let decompiled = decompile(&program);
+        assert_eq!(
+            decompiled.to_string(),
+            "\
+// r5 is the instruction pointer
+    r4 = 123
+    do {
+        r4 &= 456
+    } while r4 != 72
+    r4 = 0
+    do {
+        r3 = r4 | 65536
+        r4 = 707129
+        loop {
+            r2 = r3 & 255
+            r4 += r2
+            r4 &= 16777215
+            r4 *= 65899
+            r4 &= 16777215
+            if 256 > r3 break
+            r2 = 0
+            loop {
+                r1 = r2 + 1
+                r1 *= 256
+                if r1 > r3 break
+                r2 += 1
+            }
+            r3 = r2
+        }
+    } while r4 != r0
+"
+        );
+        assert!(decompiled.constants().contains(&(7, 707129)));
+    }
+
+    #[test]
+    fn test_computed_goto() {
+        let program: Program = "#ip 3\naddr 3 0 3\nseti 5 0 3\naddi 1 1 1\nmulr 3 3 3"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            decompile(&program).to_string(),
+            "\
+// r3 is the instruction pointer
+    goto L(1 + r0)
+L1:
+    halt
+L2:
+    r1 += 1
+L3:
+    halt
+"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Analysis of programs like day 21's, which generate a sequence of values and halt when one of
+//! them equals register 0
+
+use super::{Instructions, Machine, Program};
+use std::collections::HashSet;
+
+/// Line comparing a register to register 0, with `eqrr`, and halting when they are equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HaltingCheck {
+    pub line: usize,
+    pub register: usize,
+}
+
+pub fn halting_check(program: &Program) -> Option<HaltingCheck> {
+    program
+        .lines
+        .iter()
+        .enumerate()
+        .find_map(|(line, program_line)| match program_line.parameters {
+            (0, register, _) | (register, 0, _)
+                if program_line.instruction == Instructions::Eqrr && register != 0 =>
+            {
+                Some(HaltingCheck { line, register })
+            }
+            _ => None,
+        })
+}
+
+/// Loop dividing a register by incrementing a quotient until `(quotient + 1) * divisor` is
+/// greater than the dividend:
+///
+/// ```text
+/// seti 0 _ quotient
+/// addi quotient 1 temp
+/// muli temp divisor temp
+/// gtrr temp dividend temp
+/// ...
+/// addi quotient 1 quotient
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivisionLoop {
+    /// Line resetting the quotient
+    pub line: usize,
+    pub quotient: usize,
+    pub dividend: usize,
+    pub divisor: usize,
+}
+
+impl DivisionLoop {
+    /// Start the last iteration of the loop, which is executed normally and exits with the
+    /// quotient
+    pub fn skip(&self, machine: &mut Machine) {
+        machine.registers[self.quotient] = machine.registers[self.dividend] / self.divisor;
+        machine.pointer = self.line + 1;
+    }
+}
+
+pub fn division_loop(program: &Program) -> Option<DivisionLoop> {
+    let lines = &program.lines;
+    (0..lines.len().saturating_sub(3)).find_map(|line| {
+        let (reset, add, mul, compare) = (
+            lines[line],
+            lines[line + 1],
+            lines[line + 2],
+            lines[line + 3],
+        );
+        let quotient = reset.parameters.2;
+        let temp = add.parameters.2;
+        let dividend = compare.parameters.1;
+        let is_division = reset.instruction == Instructions::Seti
+            && reset.parameters.0 == 0
+            && add.instruction == Instructions::Addi
+            && add.parameters.0 == quotient
+            && add.parameters.1 == 1
+            && mul.instruction == Instructions::Muli
+            && mul.parameters.0 == temp
+            && mul.parameters.2 == temp
+            && compare.instruction == Instructions::Gtrr
+            && compare.parameters.0 == temp
+            && compare.parameters.2 == temp
+            && mul.parameters.1 > 0
+            && [quotient, temp, dividend]
+                .iter()
+                .all(|register| Some(*register) != program.ip)
+            && quotient != temp
+            && dividend != quotient
+            && dividend != temp
+            && lines[line + 4..].iter().any(|increment| {
+                increment.instruction == Instructions::Addi
+                    && increment.parameters == (quotient, 1, quotient)
+            });
+        if is_division {
+            Some(DivisionLoop {
+                line,
+                quotient,
+                dividend,
+                divisor: mul.parameters.1,
+            })
+        } else {
+            None
+        }
+    })
+}
+
+/// Values compared to register 0 by the halting check, computed with the division loop skipped
+pub struct HaltingValues {
+    machine: Machine,
+    check: HaltingCheck,
+    division: Option<DivisionLoop>,
+}
+
+impl HaltingValues {
+    pub fn new(program: Program, register_count: usize) -> Option<Self> {
+        let check = halting_check(&program)?;
+        let division = division_loop(&program);
+        Some(Self {
+            machine: Machine::new(program, register_count),
+            check,
+            division,
+        })
+    }
+
+    /// Run until the halting check, and return `false` if the program halted first
+    fn run_to_check(&mut self) -> bool {
+        loop {
+            if self.machine.pointer == self.check.line {
+                return true;
+            }
+            match self.division {
+                Some(division) if self.machine.pointer == division.line => {
+                    division.skip(&mut self.machine)
+                }
+                _ => {
+                    if !self.machine.step() {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+
+    /// First value, which halts the program after the fewest instructions, and last distinct
+    /// value before the machine state repeats, which halts it after the most
+    pub fn first_and_last(mut self) -> Option<(usize, usize)> {
+        let mut states = HashSet::new();
+        let mut values = HashSet::new();
+        let mut first = None;
+        let mut last = None;
+        while self.run_to_check() && states.insert(self.machine.registers.clone()) {
+            let value = self.machine.registers[self.check.register];
+            first.get_or_insert(value);
+            if values.insert(value) {
+                last = Some(value);
+            }
+            self.machine.step();
+        }
+        Some((first?, last?))
+    }
+}
+
+impl Iterator for HaltingValues {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.run_to_check() {
+            return None;
+        }
+        let value = self.machine.registers[self.check.register];
+        self.machine.step();
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../../tests/day21/input");
+
+    #[test]
+    fn test_analysis() {
+        let program: Program = INPUT.parse().unwrap();
+        assert_eq!(
+            halting_check(&program),
+            Some(HaltingCheck {
+                line: 28,
+                register: 4
+            })
+        );
+        assert_eq!(
+            division_loop(&program),
+            Some(DivisionLoop {
+                line: 17,
+                quotient: 2,
+                dividend: 3,
+                divisor: 256,
+            })
+        );
+        assert_eq!(halting_check(&"seti 0 0 1".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_halting_values() {
+        let program: Program = INPUT.parse().unwrap();
+        let values: Vec<_> = HaltingValues::new(program.clone(), 6)
+            .unwrap()
+            .take(3)
+            .collect();
+        assert_eq!(values[0], 2985446);
+
+        // Same values without skipping the division loop
+        let mut machine = Machine::new(program.clone(), 6);
+        for value in values {
+            machine.run_until(|machine| machine.pointer == 28);
+            assert_eq!(machine.registers[4], value);
+            machine.step();
+        }
+
+        assert_eq!(
+            HaltingValues::new(program, 6).unwrap().first_and_last(),
+            Some((2985446, 12502875))
+        );
+    }
+}
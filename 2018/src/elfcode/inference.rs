@@ -0,0 +1,276 @@
+//! Deduce the operation of each opcode number from samples of its effect on the registers, like in
+//! day 16
+
+use super::{Instructions, ProgramLine};
+use std::fmt;
+
+/// Something that can be executed by an instruction. Besides `Instructions`, any closure taking
+/// the three parameters and the registers can be used.
+pub trait Operation {
+    fn apply(&self, parameters: (usize, usize, usize), registers: &mut [usize]);
+}
+
+impl Operation for Instructions {
+    fn apply(&self, parameters: (usize, usize, usize), registers: &mut [usize]) {
+        Instructions::apply(*self, parameters, registers)
+    }
+}
+
+impl<F: Fn((usize, usize, usize), &mut [usize])> Operation for F {
+    fn apply(&self, parameters: (usize, usize, usize), registers: &mut [usize]) {
+        self(parameters, registers)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub before: Vec<usize>,
+    pub opcode: usize,
+    pub parameters: (usize, usize, usize),
+    pub after: Vec<usize>,
+}
+
+impl Sample {
+    pub fn matches(&self, operation: &impl Operation) -> bool {
+        let mut registers = self.before.clone();
+        operation.apply(self.parameters, &mut registers);
+        registers == self.after
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InferenceError {
+    /// Index of a sample using an opcode greater than the number of operations
+    UnknownOpcode(usize),
+    /// No operation is possible for `opcode`. `samples` are the indexes of the samples that ruled
+    /// out some of its operations, the others being assigned to other opcodes.
+    Contradiction { opcode: usize, samples: Vec<usize> },
+    /// Indexes of the operations still possible for each opcode that isn't resolved
+    Ambiguous(Vec<(usize, Vec<usize>)>),
+}
+
+impl fmt::Display for InferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InferenceError::UnknownOpcode(sample) => {
+                write!(f, "sample {} uses an unknown opcode", sample)
+            }
+            InferenceError::Contradiction { opcode, samples } => write!(
+                f,
+                "no operation matches opcode {} (samples {:?})",
+                opcode, samples
+            ),
+            InferenceError::Ambiguous(candidates) => {
+                write!(f, "ambiguous opcodes:")?;
+                for (opcode, operations) in candidates {
+                    write!(f, " {} may be {:?};", opcode, operations)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InferenceError {}
+
+/// Operation of each opcode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpcodeTable<O> {
+    pub operations: Vec<O>,
+}
+
+impl<O> OpcodeTable<O> {
+    pub fn get(&self, opcode: usize) -> Option<&O> {
+        self.operations.get(opcode)
+    }
+}
+
+impl OpcodeTable<Instructions> {
+    /// Translate a line of numeric opcode and parameters into a line of the program of days 19
+    /// and 21
+    pub fn program_line(
+        &self,
+        opcode: usize,
+        parameters: (usize, usize, usize),
+    ) -> Option<ProgramLine> {
+        Some(ProgramLine {
+            instruction: *self.get(opcode)?,
+            parameters,
+        })
+    }
+}
+
+impl<O: fmt::Display> fmt::Display for OpcodeTable<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (opcode, operation) in self.operations.iter().enumerate() {
+            writeln!(f, "{:2} {}", opcode, operation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the operation of each opcode, from `0` to `operations.len() - 1`, each operation being
+/// used by exactly one opcode.
+pub fn infer<O: Operation + Clone>(
+    operations: &[O],
+    samples: &[Sample],
+) -> Result<OpcodeTable<O>, InferenceError> {
+    let count = operations.len();
+    // For each opcode and operation, whether it is still possible, and the sample that ruled it
+    // out if any
+    let mut candidates = vec![vec![true; count]; count];
+    let mut ruled_out_by = vec![vec![None; count]; count];
+
+    for (index, sample) in samples.iter().enumerate() {
+        let opcode = sample.opcode;
+        if opcode >= count {
+            return Err(InferenceError::UnknownOpcode(index));
+        }
+        for (operation, candidate) in candidates[opcode].iter_mut().enumerate() {
+            if *candidate && !sample.matches(&operations[operation]) {
+                *candidate = false;
+                ruled_out_by[opcode][operation] = Some(index);
+            }
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for opcode in 0..count {
+            let possible: Vec<_> = (0..count).filter(|&o| candidates[opcode][o]).collect();
+            match possible[..] {
+                [] => {
+                    let mut samples: Vec<_> =
+                        ruled_out_by[opcode].iter().flatten().cloned().collect();
+                    samples.sort_unstable();
+                    samples.dedup();
+                    return Err(InferenceError::Contradiction { opcode, samples });
+                }
+                // The operation can't be used by other opcodes
+                [operation] => {
+                    for other in (0..count).filter(|&other| other != opcode) {
+                        if candidates[other][operation] {
+                            candidates[other][operation] = false;
+                            changed = true;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // An operation possible for a single opcode must be used by it
+        for operation in 0..count {
+            let opcodes: Vec<_> = (0..count).filter(|&o| candidates[o][operation]).collect();
+            if let [opcode] = opcodes[..] {
+                for other in (0..count).filter(|&other| other != operation) {
+                    if candidates[opcode][other] {
+                        candidates[opcode][other] = false;
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut table = Vec::new();
+    let mut ambiguous = Vec::new();
+    for (opcode, candidates) in candidates.iter().enumerate() {
+        let possible: Vec<_> = (0..count).filter(|&o| candidates[o]).collect();
+        match possible[..] {
+            [operation] => table.push(operations[operation].clone()),
+            _ => ambiguous.push((opcode, possible)),
+        }
+    }
+    if ambiguous.is_empty() {
+        Ok(OpcodeTable { operations: table })
+    } else {
+        Err(InferenceError::Ambiguous(ambiguous))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::INSTRUCTIONS;
+
+    fn sample(before: [usize; 2], line: [usize; 4], after: [usize; 2]) -> Sample {
+        Sample {
+            before: before.to_vec(),
+            opcode: line[0],
+            parameters: (line[1], line[2], line[3]),
+            after: after.to_vec(),
+        }
+    }
+
+    type Closure = fn((usize, usize, usize), &mut [usize]);
+
+    #[test]
+    fn test_infer() {
+        let add: Closure = |(a, b, c), registers| registers[c] = registers[a] + registers[b];
+        let mul: Closure = |(a, b, c), registers| registers[c] = registers[a] * registers[b];
+        let set: Closure = |(a, _, c), registers| registers[c] = a;
+        let operations = [add, mul, set];
+
+        // 2 + 2 == 2 * 2, but opcode 1 can only be a multiplication
+        let samples = [
+            sample([2, 2], [0, 0, 1, 1], [2, 4]),
+            sample([3, 2], [1, 0, 1, 1], [3, 6]),
+        ];
+        let table = infer(&operations, &samples).unwrap();
+        let mut registers = [3, 4];
+        table.get(0).unwrap().apply((0, 1, 0), &mut registers);
+        assert_eq!(registers, [7, 4]);
+
+        assert_eq!(
+            infer(&operations, &samples[..1]).unwrap_err(),
+            InferenceError::Ambiguous(vec![
+                (0, vec![0, 1]),
+                (1, vec![0, 1, 2]),
+                (2, vec![0, 1, 2])
+            ])
+        );
+        assert_eq!(
+            infer(
+                &operations,
+                &[samples[0].clone(), sample([1, 0], [0, 0, 0, 0], [5, 0])]
+            )
+            .unwrap_err(),
+            InferenceError::Contradiction {
+                opcode: 0,
+                samples: vec![0, 1]
+            }
+        );
+        assert_eq!(
+            infer(&operations, &[sample([0, 0], [3, 0, 0, 0], [0, 0])]).unwrap_err(),
+            InferenceError::UnknownOpcode(0)
+        );
+    }
+
+    #[test]
+    fn test_instructions() {
+        let mut samples = Vec::new();
+        for (opcode, instruction) in INSTRUCTIONS.iter().enumerate() {
+            for before in [[3, 2, 1, 2], [0, 1, 5, 7], [6, 3, 2, 6]] {
+                for parameters in [(0, 3, 1), (2, 1, 0), (3, 3, 2)] {
+                    let mut after = before;
+                    instruction.apply(parameters, &mut after);
+                    samples.push(Sample {
+                        before: before.to_vec(),
+                        opcode: 15 - opcode,
+                        parameters,
+                        after: after.to_vec(),
+                    });
+                }
+            }
+        }
+        let table = infer(&INSTRUCTIONS, &samples).unwrap();
+        assert_eq!(table.get(0), Some(&Instructions::Eqrr));
+        assert_eq!(
+            table.program_line(15, (1, 2, 3)).unwrap().to_string(),
+            "addr 1 2 3"
+        );
+        assert!(table.to_string().starts_with(" 0 eqrr\n 1 eqri\n"));
+    }
+}
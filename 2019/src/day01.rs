@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 fn fuel_required(mass: u32) -> u32 {
     (mass / 3).saturating_sub(2)
 }
@@ -11,16 +13,21 @@ fn fuel_required_with_requirement(mut mass: u32) -> u32 {
     result
 }
 
-#[allow(dead_code)]
 fn total_fuel_required<T: Iterator<Item = u32>>(iterator: T) -> u32 {
     iterator.map(fuel_required).sum()
 }
 
-#[allow(dead_code)]
 fn total_fuel_required_with_requirement<T: Iterator<Item = u32>>(iterator: T) -> u32 {
     iterator.map(fuel_required_with_requirement).sum()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| total_fuel_required(input.flat_map(|line| line.parse())).to_string(),
+    part_2: Some(|input| {
+        total_fuel_required_with_requirement(input.flat_map(|line| line.parse())).to_string()
+    }),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
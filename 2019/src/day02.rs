@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 type Value = u32;
 type Program = Vec<Value>;
 type ProgramSlice = [Value];
@@ -29,7 +31,6 @@ fn run_program_with_input(mut program: Program, noun: Value, verb: Value) -> Val
     program[0]
 }
 
-#[allow(dead_code)]
 fn find_verb_noun(program: &ProgramSlice) -> Value {
     for verb in 0..=99 {
         for noun in 0..=99 {
@@ -41,6 +42,20 @@ fn find_verb_noun(program: &ProgramSlice) -> Value {
     0
 }
 
+fn parse_program(line: &str) -> Program {
+    line.split(',').filter_map(|n| n.parse().ok()).collect()
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |mut input| {
+        let program = parse_program(&input.next().expect("No input"));
+        run_program_with_input(program, 12, 2).to_string()
+    },
+    part_2: Some(|mut input| {
+        find_verb_noun(&parse_program(&input.next().expect("No input"))).to_string()
+    }),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,12 +97,7 @@ mod tests {
     }
 
     fn get_program() -> Program {
-        util::input(2)
-            .next()
-            .expect("No input")
-            .split(',')
-            .filter_map(|n| n.parse().ok())
-            .collect()
+        parse_program(&util::input(2).next().expect("No input"))
     }
     #[test]
     fn part_1() {
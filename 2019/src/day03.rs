@@ -1,4 +1,5 @@
 use crate::point::Point;
+use aoc::runner::Solution;
 use itertools::iproduct;
 
 type Line = (Point, Point);
@@ -59,43 +60,56 @@ fn wire_intersections<'a>(
     .filter(|(steps, _)| *steps != 0)
 }
 
-#[allow(dead_code)]
 fn wire_intersection_min_distance(wire1: &[Line], wire2: &[Line]) -> Option<i32> {
     wire_intersections(wire1, wire2)
         .map(|(_, p)| Point::new(0, 0).distance(p))
         .min()
 }
 
-#[allow(dead_code)]
 fn wire_intersection_min_steps(wire1: &[Line], wire2: &[Line]) -> Option<i32> {
     wire_intersections(wire1, wire2)
         .map(|(steps, _)| steps)
         .min()
 }
 
+fn parse_wire(s: &str) -> Vec<Line> {
+    s.split(',')
+        .scan(Point::new(0, 0), |previous, path| {
+            let distance = path[1..].parse::<i32>().expect("Failed to parse distance");
+            let next = match path.chars().nth(0).expect("Failed to parse direction") {
+                'R' => Point::new(previous.x + distance, previous.y),
+                'L' => Point::new(previous.x - distance, previous.y),
+                'U' => Point::new(previous.x, previous.y + distance),
+                'D' => Point::new(previous.x, previous.y - distance),
+                d => panic!("Invalid direction {}", d),
+            };
+            let result = (*previous, next);
+            *previous = next;
+            Some(result)
+        })
+        .collect()
+}
+
+fn part_1(input: impl Iterator<Item = String>) -> i32 {
+    let wires: Vec<_> = input.map(|line| parse_wire(&line)).collect();
+    wire_intersection_min_distance(&wires[0], &wires[1]).expect("No intersection")
+}
+
+fn part_2(input: impl Iterator<Item = String>) -> i32 {
+    let wires: Vec<_> = input.map(|line| parse_wire(&line)).collect();
+    wire_intersection_min_steps(&wires[0], &wires[1]).expect("No intersection")
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util;
 
-    fn parse_wire(s: &str) -> Vec<Line> {
-        s.split(',')
-            .scan(Point::new(0, 0), |previous, path| {
-                let distance = path[1..].parse::<i32>().expect("Failed to parse distance");
-                let next = match path.chars().nth(0).expect("Failed to parse direction") {
-                    'R' => Point::new(previous.x + distance, previous.y),
-                    'L' => Point::new(previous.x - distance, previous.y),
-                    'U' => Point::new(previous.x, previous.y + distance),
-                    'D' => Point::new(previous.x, previous.y - distance),
-                    d => panic!("Invalid direction {}", d),
-                };
-                let result = (*previous, next);
-                *previous = next;
-                Some(result)
-            })
-            .collect()
-    }
-
     #[test]
     fn parse_wire_test() {
         assert_eq!(
@@ -167,12 +181,8 @@ mod tests {
     }
 
     #[test]
-    fn part_1() {
-        let wires = util::input(3).collect::<Vec<String>>();
-        assert_eq!(
-            wire_intersection_min_distance(&parse_wire(&wires[0]), &parse_wire(&wires[1])),
-            util::answer(3, 1)
-        );
+    fn part_1_test() {
+        assert_eq!(Some(part_1(util::input(3))), util::answer(3, 1));
     }
 
     #[test]
@@ -197,11 +207,7 @@ mod tests {
         );
     }
     #[test]
-    fn part_2() {
-        let wires = util::input(3).collect::<Vec<String>>();
-        assert_eq!(
-            wire_intersection_min_steps(&parse_wire(&wires[0]), &parse_wire(&wires[1])),
-            util::answer(3, 2)
-        );
+    fn part_2_test() {
+        assert_eq!(Some(part_2(util::input(3))), util::answer(3, 2));
     }
 }
@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use itertools::Itertools;
 
 struct Digits {
@@ -21,17 +22,14 @@ impl Iterator for Digits {
     }
 }
 
-#[allow(dead_code)]
 fn are_digits_increasing(n: u32) -> bool {
     Digits::new(n).tuple_windows().all(|(a, b)| a >= b)
 }
 
-#[allow(dead_code)]
 fn have_same_digits_pair(n: u32) -> bool {
     Digits::new(n).tuple_windows().any(|(a, b)| a == b)
 }
 
-#[allow(dead_code)]
 fn have_same_digits_pair2(n: u32) -> bool {
     let mut pairs = Digits::new(n)
         .tuple_windows()
@@ -48,6 +46,32 @@ fn have_same_digits_pair2(n: u32) -> bool {
     false
 }
 
+fn parse_range(line: &str) -> (u32, u32) {
+    let numbers: Vec<u32> = line.split('-').filter_map(|n| n.parse().ok()).collect();
+    (numbers[0], numbers[1])
+}
+
+fn part_1(mut input: impl Iterator<Item = String>) -> usize {
+    let (start, end) = parse_range(&input.next().expect("No input"));
+    (start..end)
+        .filter(|n| are_digits_increasing(*n))
+        .filter(|n| have_same_digits_pair(*n))
+        .count()
+}
+
+fn part_2(mut input: impl Iterator<Item = String>) -> usize {
+    let (start, end) = parse_range(&input.next().expect("No input"));
+    (start..end)
+        .filter(|n| are_digits_increasing(*n))
+        .filter(|n| have_same_digits_pair2(*n))
+        .count()
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,41 +107,13 @@ mod tests {
         assert_eq!(have_same_digits_pair2(111_122), true);
     }
 
-    fn input_numbers() -> (u32, u32) {
-        let numbers: Vec<u32> = util::input(4)
-            .next()
-            .expect("No input")
-            .split('-')
-            .filter_map(|n| n.parse().ok())
-            .collect();
-        (numbers[0], numbers[1])
-    }
-
     #[test]
-    fn part_1() {
-        let (start, end) = input_numbers();
-        assert_eq!(
-            Some(
-                (start..end)
-                    .filter(|n| are_digits_increasing(*n))
-                    .filter(|n| have_same_digits_pair(*n))
-                    .count()
-            ),
-            util::answer(4, 1)
-        );
+    fn part_1_test() {
+        assert_eq!(Some(part_1(util::input(4))), util::answer(4, 1));
     }
 
     #[test]
-    fn part_2() {
-        let (start, end) = input_numbers();
-        assert_eq!(
-            Some(
-                (start..end)
-                    .filter(|n| are_digits_increasing(*n))
-                    .filter(|n| have_same_digits_pair2(*n))
-                    .count()
-            ),
-            util::answer(4, 2)
-        );
+    fn part_2_test() {
+        assert_eq!(Some(part_2(util::input(4))), util::answer(4, 2));
     }
 }
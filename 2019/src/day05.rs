@@ -1,26 +1,38 @@
+use crate::intcode::Program;
+use aoc::runner::Solution;
+
+/// Last output of the diagnostic program, run with the ID of the system to test
+fn diagnostic_code(mut program: Program, system: i64) -> i64 {
+    program
+        .run(&[system])
+        .unwrap()
+        .into_iter()
+        .last()
+        .expect("No output")
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| diagnostic_code(Program::from_input(input), 1).to_string(),
+    part_2: Some(|input| diagnostic_code(Program::from_input(input), 5).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
-    use crate::intcode::Program;
+    use super::*;
     use crate::util;
 
-    fn get_program() -> Program {
-        util::input(5).next().expect("No input").parse().unwrap()
-    }
-
     #[test]
     fn part_1() {
-        let mut program = get_program();
         assert_eq!(
-            program.run(&[1]).unwrap().into_iter().last(),
+            Some(diagnostic_code(Program::from_input(util::input(5)), 1)),
             util::answer(5, 1)
         );
     }
 
     #[test]
     fn part_2() {
-        let mut program = get_program();
         assert_eq!(
-            program.run(&[5]).unwrap().into_iter().last(),
+            Some(diagnostic_code(Program::from_input(util::input(5)), 5)),
             util::answer(5, 2)
         );
     }
@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use std::collections::HashMap;
 
 type Map = HashMap<String, Vec<String>>;
@@ -12,7 +13,6 @@ fn count_suborbits(center: &str, map: &Map) -> u32 {
         .sum()
 }
 
-#[allow(dead_code)]
 fn count_orbits(center: &str, map: &Map) -> u32 {
     count_suborbits(center, map)
         + iter_orbits(center, map)
@@ -40,28 +40,32 @@ fn count_orbit_transfers_rec(center: &str, map: &Map) -> (Option<u32>, Option<u3
     }
 }
 
-#[allow(dead_code)]
 fn count_orbit_transfers(map: &Map) -> u32 {
     let (a, b) = count_orbit_transfers_rec("COM", map);
     a.unwrap() + b.unwrap()
 }
 
+fn parse_map<T: Iterator<Item = String>>(lines: T) -> Map {
+    let mut map = HashMap::new();
+    for line in lines {
+        let (center, orbit) = line.split_at(line.find(')').expect("Failed to parse line"));
+        map.entry(center.to_string())
+            .or_insert_with(Vec::new)
+            .push(orbit[1..].to_string());
+    }
+    map
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| count_orbits("COM", &parse_map(input)).to_string(),
+    part_2: Some(|input| count_orbit_transfers(&parse_map(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util;
 
-    fn parse_map<T: Iterator<Item = String>>(lines: T) -> Map {
-        let mut map = HashMap::new();
-        for line in lines {
-            let (center, orbit) = line.split_at(line.find(')').expect("Failed to parse line"));
-            map.entry(center.to_string())
-                .or_insert_with(Vec::new)
-                .push(orbit[1..].to_string());
-        }
-        map
-    }
-
     #[test]
     fn iter_digits_test() {
         assert_eq!(
@@ -1,7 +1,7 @@
 use crate::intcode::{Program, ProgramResult, Value};
+use aoc::runner::Solution;
 use itertools::Itertools;
 
-#[allow(dead_code)]
 fn run_amplifiers(program: &Program) -> Value {
     (0..5)
         .permutations(5)
@@ -17,7 +17,6 @@ fn run_amplifiers(program: &Program) -> Value {
         .unwrap()
 }
 
-#[allow(dead_code)]
 fn run_amplifiers_with_feedback_loop(program: &Program) -> Value {
     (5..10)
         .permutations(5)
@@ -50,13 +49,20 @@ fn run_amplifiers_with_feedback_loop(program: &Program) -> Value {
         .unwrap()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| run_amplifiers(&Program::from_input(input)).to_string(),
+    part_2: Some(|input| {
+        run_amplifiers_with_feedback_loop(&Program::from_input(input)).to_string()
+    }),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util;
 
     fn get_program() -> Program {
-        util::input(7).next().expect("No input").parse().unwrap()
+        Program::from_input(util::input(7))
     }
 
     #[test]
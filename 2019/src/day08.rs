@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use itertools::iproduct;
 
 const WIDTH: usize = 25;
@@ -12,7 +13,6 @@ fn layer(image: &[u8], n: usize) -> Option<&[u8]> {
     }
 }
 
-#[allow(dead_code)]
 fn iter_layers(image: &[u8]) -> impl Iterator<Item = &[u8]> {
     (0..)
         .map(move |n| layer(image, n))
@@ -20,7 +20,6 @@ fn iter_layers(image: &[u8]) -> impl Iterator<Item = &[u8]> {
         .filter_map(|layer| layer)
 }
 
-#[allow(dead_code)]
 fn format_image(image: &[u8]) -> String {
     let mut result = String::new();
     for (y, x) in iproduct!(0..HEIGHT, 0..WIDTH) {
@@ -35,35 +34,44 @@ fn format_image(image: &[u8]) -> String {
     result
 }
 
+fn parse_image(input: impl Iterator<Item = String>) -> Vec<u8> {
+    input
+        .flat_map(|line| line.into_bytes().into_iter().map(|b| b - b'0'))
+        .collect()
+}
+
+/// Number of 1 digits multiplied by the number of 2 digits of the layer with the fewest 0 digits
+fn checksum(image: &[u8]) -> usize {
+    let layer = iter_layers(image)
+        .min_by_key(|layer| bytecount::count(layer, 0))
+        .expect("Layer not found");
+    bytecount::count(layer, 1) * bytecount::count(layer, 2)
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| checksum(&parse_image(input)).to_string(),
+    part_2: Some(|input| format_image(&parse_image(input))),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ocr::ocr;
     use crate::util;
+    use aoc::answer::Answer;
 
     fn get_image() -> Vec<u8> {
-        util::input(8)
-            .flat_map(|line| line.into_bytes().into_iter().map(|b| b - b'0'))
-            .collect()
+        parse_image(util::input(8))
     }
 
     #[test]
     fn part_1() {
-        let image = get_image();
-        let layer = iter_layers(&image)
-            .min_by_key(|layer| bytecount::count(layer, 0))
-            .expect("Layer not found");
-
-        assert_eq!(
-            Some(bytecount::count(layer, 1) * bytecount::count(layer, 2)),
-            util::answer(8, 1)
-        );
+        assert_eq!(Some(checksum(&get_image())), util::answer(8, 1));
     }
 
     #[test]
     fn part_2() {
         let image = get_image();
         let formated_image = format_image(&image);
-        assert_eq!(Some(ocr(&formated_image)), util::answer(8, 2));
+        assert_eq!(Some(Answer::image(&formated_image)), util::answer(8, 2));
     }
 }
@@ -1,10 +1,25 @@
+use crate::intcode::Program;
+use aoc::runner::Solution;
+
+/// Single output of the BOOST program, run in the given mode
+fn boost_keycode(mut program: Program, mode: i64) -> i64 {
+    let output = program.run(&[mode]).unwrap();
+    assert_eq!(output.len(), 1, "Malfunctioning opcodes: {:?}", output);
+    output[0]
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| boost_keycode(Program::from_input(input), 1).to_string(),
+    part_2: Some(|input| boost_keycode(Program::from_input(input), 2).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
-    use crate::intcode::Program;
+    use super::*;
     use crate::util;
 
     fn get_program() -> Program {
-        util::input(9).next().expect("No input").parse().unwrap()
+        Program::from_input(util::input(9))
     }
 
     #[test]
@@ -37,17 +52,11 @@ mod tests {
 
     #[test]
     fn part_1() {
-        let mut program = get_program();
-        let result = program.run(&[1]).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(Some(result[0]), util::answer(9, 1));
+        assert_eq!(Some(boost_keycode(get_program(), 1)), util::answer(9, 1));
     }
 
     #[test]
     fn part_2() {
-        let mut program = get_program();
-        let result = program.run(&[2]).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(Some(result[0]), util::answer(9, 2));
+        assert_eq!(Some(boost_keycode(get_program(), 2)), util::answer(9, 2));
     }
 }
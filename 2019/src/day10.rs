@@ -1,5 +1,7 @@
 use crate::point::Point;
+use aoc::runner::Solution;
 use std::collections::HashSet;
+use std::convert::TryInto;
 
 fn compute_closest_point(p: Point) -> Point {
     let mut a = p.x.abs().max(p.y.abs());
@@ -28,7 +30,6 @@ fn count_asteroids_in_sight(map: &HashSet<Point>, origin: Point) -> usize {
     is_in_sight.len()
 }
 
-#[allow(dead_code)]
 fn find_best_asteroid(map: &HashSet<Point>) -> (Point, usize) {
     map.iter()
         .map(|origin| (*origin, count_asteroids_in_sight(&map, *origin)))
@@ -41,7 +42,6 @@ fn compute_angle(p: Point) -> f32 {
     -(p.x as f32).atan2(p.y as f32)
 }
 
-#[allow(dead_code)]
 fn find_200th_shoted_asteroid(mut map: HashSet<Point>) -> Option<Point> {
     let origin = find_best_asteroid(&map).0;
     map.remove(&origin);
@@ -77,6 +77,32 @@ fn find_200th_shoted_asteroid(mut map: HashSet<Point>) -> Option<Point> {
     None
 }
 
+fn parse_map(input: impl Iterator<Item = String>) -> HashSet<Point> {
+    input
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.into_bytes()
+                .into_iter()
+                .enumerate()
+                .filter_map(move |(x, ch)| {
+                    if ch == b'#' {
+                        Some(Point::new(x.try_into().unwrap(), y.try_into().unwrap()))
+                    } else {
+                        None
+                    }
+                })
+        })
+        .collect()
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| find_best_asteroid(&parse_map(input)).1.to_string(),
+    part_2: Some(|input| {
+        let point = find_200th_shoted_asteroid(parse_map(input)).expect("Asteroid not found");
+        (point.x * 100 + point.y).to_string()
+    }),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,21 +225,7 @@ mod tests {
     }
 
     fn get_map() -> HashSet<Point> {
-        util::input(10)
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.into_bytes()
-                    .into_iter()
-                    .enumerate()
-                    .filter_map(move |(x, ch)| {
-                        if ch == b'#' {
-                            Some(Point::new(x.try_into().unwrap(), y.try_into().unwrap()))
-                        } else {
-                            None
-                        }
-                    })
-            })
-            .collect()
+        parse_map(util::input(10))
     }
 
     #[test]
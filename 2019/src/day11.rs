@@ -1,9 +1,9 @@
 use crate::intcode::{Program, ProgramResult, Value};
 use crate::point::Point;
+use aoc::runner::Solution;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-#[allow(dead_code)]
 fn run_robot(program: &mut Program, start_color: Value) -> HashMap<Point, Value> {
     let mut point = Point::new(0, 0);
     let mut points = HashMap::new();
@@ -31,7 +31,6 @@ fn run_robot(program: &mut Program, start_color: Value) -> HashMap<Point, Value>
     points
 }
 
-#[allow(dead_code)]
 fn format_image(points: &HashMap<Point, Value>) -> String {
     let white_points: HashSet<_> = points
         .iter()
@@ -64,14 +63,23 @@ fn format_image(points: &HashMap<Point, Value>) -> String {
     result
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| {
+        run_robot(&mut Program::from_input(input), 0)
+            .len()
+            .to_string()
+    },
+    part_2: Some(|input| format_image(&run_robot(&mut Program::from_input(input), 1))),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ocr::ocr;
     use crate::util;
+    use aoc::answer::Answer;
 
     fn get_program() -> Program {
-        util::input(11).next().expect("No input").parse().unwrap()
+        Program::from_input(util::input(11))
     }
 
     #[test]
@@ -86,6 +94,6 @@ mod tests {
         let mut program = get_program();
         let points = run_robot(&mut program, 1);
         let formated_image = format_image(&points);
-        assert_eq!(Some(ocr(&formated_image)), util::answer(11, 2));
+        assert_eq!(Some(Answer::image(&formated_image)), util::answer(11, 2));
     }
 }
@@ -1,4 +1,5 @@
 use crate::util;
+use aoc::runner::Solution;
 use itertools::Itertools;
 use std::cmp::Ordering;
 
@@ -44,7 +45,6 @@ struct Moon {
 }
 
 impl Moon {
-    #[allow(dead_code)]
     fn new(position: Point3D) -> Self {
         Self {
             position,
@@ -53,7 +53,6 @@ impl Moon {
     }
 }
 
-#[allow(dead_code)]
 fn step(moons: &[Moon]) -> Vec<Moon> {
     let mut new_moons = moons.to_vec();
 
@@ -80,7 +79,6 @@ fn step(moons: &[Moon]) -> Vec<Moon> {
     new_moons
 }
 
-#[allow(dead_code)]
 fn compute_energy(moons: &[Moon]) -> i64 {
     moons
         .iter()
@@ -102,7 +100,6 @@ fn compute_energy(moons: &[Moon]) -> i64 {
         .sum()
 }
 
-#[allow(dead_code)]
 fn count_cyclic_steps(moons: &[Moon]) -> i64 {
     let mut result = 1;
     for d in 0..3 {
@@ -126,6 +123,26 @@ fn count_cyclic_steps(moons: &[Moon]) -> i64 {
     result
 }
 
+fn parse_moons(input: impl Iterator<Item = String>) -> Vec<Moon> {
+    input
+        .filter_map(|s| s.parse::<Point3D>().ok().map(Moon::new))
+        .collect()
+}
+
+/// Total energy of the moons after 1000 steps
+fn part_1(input: impl Iterator<Item = String>) -> i64 {
+    let mut moons = parse_moons(input);
+    for _ in 0..1000 {
+        moons = step(&moons);
+    }
+    compute_energy(&moons)
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| count_cyclic_steps(&parse_moons(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,16 +189,8 @@ mod tests {
     }
 
     #[test]
-    fn part_1() {
-        let mut moons: Vec<_> = util::input(12)
-            .filter_map(|s| s.parse::<Point3D>().ok().map(Moon::new))
-            .collect();
-
-        for _ in 0..1000 {
-            moons = step(&moons);
-        }
-
-        assert_eq!(Some(compute_energy(&moons)), util::answer(12, 1));
+    fn part_1_test() {
+        assert_eq!(Some(part_1(util::input(12))), util::answer(12, 1));
     }
 
     #[test]
@@ -198,9 +207,9 @@ mod tests {
 
     #[test]
     fn part_2() {
-        let moons: Vec<_> = util::input(12)
-            .filter_map(|s| s.parse::<Point3D>().ok().map(Moon::new))
-            .collect();
-        assert_eq!(Some(count_cyclic_steps(&moons)), util::answer(12, 2));
+        assert_eq!(
+            Some(count_cyclic_steps(&parse_moons(util::input(12)))),
+            util::answer(12, 2)
+        );
     }
 }
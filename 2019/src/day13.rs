@@ -1,5 +1,7 @@
-use crate::intcode::Value;
+use crate::intcode::{Program, ProgramResult, Value};
+use aoc::runner::Solution;
 use itertools::Itertools;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 
 fn as_usize(v: Value) -> usize {
@@ -13,7 +15,6 @@ struct Screen {
 }
 
 impl Screen {
-    #[allow(dead_code)]
     fn new(initial_instructions: &[Value]) -> Self {
         let mut max_x = 0;
         let mut max_y = 0;
@@ -72,59 +73,66 @@ impl Screen {
     }
 }
 
+/// Number of block tiles on the screen when the game starts
+fn count_blocks(mut program: Program) -> usize {
+    program
+        .run(&[])
+        .unwrap()
+        .iter()
+        .tuples()
+        .filter(|(_x, _y, id)| **id == 2)
+        .count()
+}
+
+/// Score after breaking all the blocks, the paddle following the ball
+fn play(mut program: Program) -> Value {
+    program.write_memory(0, 2);
+
+    let mut screen = match program.run(&[]) {
+        ProgramResult::NeedInput(instructions) => Screen::new(&instructions),
+        _ => panic!("Nope"),
+    };
+
+    loop {
+        let joystick = match screen.paddle_x.cmp(&screen.ball_x) {
+            Ordering::Less => 1,
+            Ordering::Greater => -1,
+            _ => 0,
+        };
+
+        match program.run(&[joystick]) {
+            ProgramResult::NeedInput(instructions) => screen.update(&instructions),
+            ProgramResult::Halt(instructions) => {
+                screen.update(&instructions);
+                break;
+            }
+        }
+    }
+
+    screen.score
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| count_blocks(Program::from_input(input)).to_string(),
+    part_2: Some(|input| play(Program::from_input(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::intcode::{Program, ProgramResult};
     use crate::util;
-    use std::cmp::Ordering;
 
     fn get_program() -> Program {
-        util::input(13).next().expect("No input").parse().unwrap()
+        Program::from_input(util::input(13))
     }
 
     #[test]
     fn part_1() {
-        let mut program = get_program();
-        let instructions = program.run(&[]).unwrap();
-        assert_eq!(
-            Some(
-                instructions
-                    .iter()
-                    .tuples()
-                    .filter(|(_x, _y, id)| **id == 2)
-                    .count()
-            ),
-            util::answer(13, 1)
-        );
+        assert_eq!(Some(count_blocks(get_program())), util::answer(13, 1));
     }
 
     #[test]
     fn part_2() {
-        let mut program = get_program();
-        program.write_memory(0, 2);
-
-        let mut screen = match program.run(&[]) {
-            ProgramResult::NeedInput(instructions) => Screen::new(&instructions),
-            _ => panic!("Nope"),
-        };
-
-        loop {
-            let joystick = match screen.paddle_x.cmp(&screen.ball_x) {
-                Ordering::Less => 1,
-                Ordering::Greater => -1,
-                _ => 0,
-            };
-
-            match program.run(&[joystick]) {
-                ProgramResult::NeedInput(instructions) => screen.update(&instructions),
-                ProgramResult::Halt(instructions) => {
-                    screen.update(&instructions);
-                    break;
-                }
-            }
-        }
-
-        assert_eq!(Some(screen.score), util::answer(13, 2));
+        assert_eq!(Some(play(get_program())), util::answer(13, 2));
     }
 }
@@ -1,6 +1,9 @@
+use aoc::runner::Solution;
+use itertools::Itertools;
 use std::collections::HashMap;
 
 type Chemical = usize;
+type Reactions = Vec<(u64, Vec<(Chemical, u64)>)>;
 type ReactionsSlice = [(u64, Vec<(Chemical, u64)>)];
 const FUEL: usize = 0;
 const ORE: usize = 10000;
@@ -51,7 +54,6 @@ fn run_reactions(
     missing_chemicals.get(&ORE).cloned()
 }
 
-#[allow(dead_code)]
 fn run_reactions_for_one_fuel(reactions: &ReactionsSlice) -> Option<u64> {
     run_reactions(reactions, &mut vec![0; reactions.len()], 1)
 }
@@ -78,7 +80,6 @@ fn run_reactions_for_ore_mildly_optimized(reactions: &ReactionsSlice, ore: u64)
     Some((ore as f64 / required_ore as f64 * fuel as f64) as u64)
 }
 
-#[allow(dead_code)]
 fn run_reactions_for_ore(reactions: &ReactionsSlice, ore: u64) -> Option<u64> {
     let mut steps = 1_000_000;
     let mut fuel = steps;
@@ -96,40 +97,51 @@ fn run_reactions_for_ore(reactions: &ReactionsSlice, ore: u64) -> Option<u64> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::util;
-    use itertools::Itertools;
-    type Reactions = Vec<(u64, Vec<(Chemical, u64)>)>;
+fn parse_quantity(chemical_ids: &mut HashMap<String, usize>, s: &str) -> (Chemical, u64) {
+    let (quantity, chemical) = s.split(' ').tuples().next().unwrap();
+    let next_id = chemical_ids.len() - 1;
+    let chemical_id = chemical_ids.entry(chemical.to_string()).or_insert(next_id);
+    (*chemical_id, quantity.parse().unwrap())
+}
 
-    fn parse_quantity(chemical_ids: &mut HashMap<String, usize>, s: &str) -> (Chemical, u64) {
-        let (quantity, chemical) = s.split(' ').tuples().next().unwrap();
-        let next_id = chemical_ids.len() - 1;
-        let chemical_id = chemical_ids.entry(chemical.to_string()).or_insert(next_id);
-        (*chemical_id, quantity.parse().unwrap())
-    }
+fn parse_reactions<S: AsRef<str>, I: Iterator<Item = S>>(iterator: I) -> Reactions {
+    let mut chemical_ids: HashMap<String, usize> = HashMap::new();
+    chemical_ids.insert("FUEL".into(), FUEL);
+    chemical_ids.insert("ORE".into(), ORE);
 
-    fn parse_reactions<S: AsRef<str>, I: Iterator<Item = S>>(iterator: I) -> Reactions {
-        let mut chemical_ids: HashMap<String, usize> = HashMap::new();
-        chemical_ids.insert("FUEL".into(), FUEL);
-        chemical_ids.insert("ORE".into(), ORE);
+    let hash: HashMap<_, _> = iterator
+        .map(|line| {
+            let (input, output) = line.as_ref().split(" => ").tuples().next().unwrap();
 
-        let hash: HashMap<_, _> = iterator
-            .map(|line| {
-                let (input, output) = line.as_ref().split(" => ").tuples().next().unwrap();
+            let inputs: Vec<_> = input
+                .split(", ")
+                .map(|s| parse_quantity(&mut chemical_ids, s))
+                .collect();
+            let (chemical, quantity) = parse_quantity(&mut chemical_ids, output);
+            (chemical, (quantity, inputs))
+        })
+        .collect();
 
-                let inputs: Vec<_> = input
-                    .split(", ")
-                    .map(|s| parse_quantity(&mut chemical_ids, s))
-                    .collect();
-                let (chemical, quantity) = parse_quantity(&mut chemical_ids, output);
-                (chemical, (quantity, inputs))
-            })
-            .collect();
+    (0..hash.len()).map(|i| hash[&i].clone()).collect()
+}
 
-        (0..hash.len()).map(|i| hash[&i].clone()).collect()
-    }
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| {
+        run_reactions_for_one_fuel(&parse_reactions(input))
+            .expect("Not enough ore")
+            .to_string()
+    },
+    part_2: Some(|input| {
+        run_reactions_for_ore(&parse_reactions(input), 1_000_000_000_000)
+            .expect("Not enough ore")
+            .to_string()
+    }),
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
 
     fn parse_reactions_from_str(s: &str) -> Reactions {
         parse_reactions(s.split('\n'))
@@ -1,5 +1,6 @@
 use crate::intcode::{Program, ProgramResult};
 use crate::point::Point;
+use aoc::runner::Solution;
 use std::collections::HashMap;
 
 fn discover(
@@ -48,7 +49,6 @@ fn discover(
     result
 }
 
-#[allow(dead_code)]
 fn start_discovering(
     program: &Program,
     discoved_places: &mut HashMap<Point, u32>,
@@ -56,31 +56,43 @@ fn start_discovering(
     discover(program, Point::new(0, 0), 0, discoved_places)
 }
 
+/// Fewest movements to reach the oxygen system
+fn part_1(program: &Program) -> u32 {
+    let mut discoved_places: HashMap<Point, u32> = HashMap::new();
+    let (_, oxygen_position) = start_discovering(program, &mut discoved_places).unwrap();
+    discoved_places[&oxygen_position]
+}
+
+/// Minutes for the oxygen to fill the area, i.e. the farthest place from the oxygen system
+fn part_2(program: &Program) -> u32 {
+    let mut discoved_places: HashMap<Point, u32> = HashMap::new();
+    let (oxygen_program, _) = start_discovering(program, &mut discoved_places).unwrap();
+    discoved_places.clear();
+    start_discovering(&oxygen_program, &mut discoved_places).unwrap();
+    discoved_places.values().cloned().max().unwrap()
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(&Program::from_input(input)).to_string(),
+    part_2: Some(|input| part_2(&Program::from_input(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util;
 
     fn get_program() -> Program {
-        util::input(15).next().expect("No input").parse().unwrap()
+        Program::from_input(util::input(15))
     }
 
     #[test]
-    fn part_1() {
-        let mut discoved_places: HashMap<Point, u32> = HashMap::new();
-        let (_, oxygen_position) = start_discovering(&get_program(), &mut discoved_places).unwrap();
-        assert_eq!(
-            discoved_places.get(&oxygen_position).cloned(),
-            util::answer(15, 1)
-        );
+    fn part_1_test() {
+        assert_eq!(Some(part_1(&get_program())), util::answer(15, 1));
     }
 
     #[test]
-    fn part_2() {
-        let mut discoved_places: HashMap<Point, u32> = HashMap::new();
-        let (oxygen_program, _) = start_discovering(&get_program(), &mut discoved_places).unwrap();
-        discoved_places.clear();
-        start_discovering(&oxygen_program, &mut discoved_places).unwrap();
-        assert_eq!(discoved_places.values().cloned().max(), util::answer(15, 2));
+    fn part_2_test() {
+        assert_eq!(Some(part_2(&get_program())), util::answer(15, 2));
     }
 }
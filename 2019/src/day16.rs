@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use std::iter;
 
 /// Naive approach, this describes simply what's requested, but can be optimized
@@ -23,7 +24,7 @@ fn run_phase_naive(signal: &[u8]) -> Vec<u8> {
 }
 
 /// Optimized approach, we skip a lot of computation by reusing the previously computed sums
-#[allow(dead_code, clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
 fn run_phase(signal: &[u8]) -> Vec<u8> {
     let mut base_sum = 0;
     let mut res = vec![0; signal.len()];
@@ -62,7 +63,6 @@ fn run_phase(signal: &[u8]) -> Vec<u8> {
 
 /// Overly optimized solution. It only works if the "offset" is greater than 5000 times the length
 /// of the input.
-#[allow(dead_code)]
 fn run_phases_for_10k_input(input: &[u8]) -> Vec<u8> {
     let offset = input
         .iter()
@@ -92,22 +92,39 @@ fn run_phases_for_10k_input(input: &[u8]) -> Vec<u8> {
     signal[..8].to_vec()
 }
 
+fn join<S: std::string::ToString, T: Iterator<Item = S>>(val: T) -> String {
+    val.map(|s| s.to_string()).collect()
+}
+
+fn parse_signal(mut input: impl Iterator<Item = String>) -> Vec<u8> {
+    input
+        .next()
+        .expect("No input")
+        .bytes()
+        .map(|b| b - b'0')
+        .collect()
+}
+
+/// First eight digits after 100 phases
+fn part_1(mut signal: Vec<u8>) -> String {
+    for _ in 0..100 {
+        signal = run_phase(&signal);
+    }
+    join(signal[..8].iter())
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(parse_signal(input)),
+    part_2: Some(|input| join(run_phases_for_10k_input(&parse_signal(input)).iter())),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util;
 
-    fn join<S: std::string::ToString, T: Iterator<Item = S>>(val: T) -> String {
-        val.map(|s| s.to_string()).collect()
-    }
-
     fn get_signal() -> Vec<u8> {
-        util::input(16)
-            .next()
-            .unwrap()
-            .bytes()
-            .map(|b| b - b'0')
-            .collect()
+        parse_signal(util::input(16))
     }
 
     #[test]
@@ -123,12 +140,8 @@ mod tests {
     }
 
     #[test]
-    fn part_1() {
-        let mut signal = get_signal();
-        for _ in 0..100 {
-            signal = run_phase(&signal);
-        }
-        assert_eq!(Some(join(signal[..8].iter())), util::answer(16, 1));
+    fn part_1_test() {
+        assert_eq!(Some(part_1(get_signal())), util::answer(16, 1));
     }
 
     #[test]
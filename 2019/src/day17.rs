@@ -1,9 +1,9 @@
-use crate::intcode::Value;
+use crate::intcode::{Program, Value};
 use crate::point::{Direction, Point};
+use aoc::runner::Solution;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 
-#[allow(dead_code)]
 fn get_state(map: &[u8]) -> (HashSet<Point>, (Point, Direction)) {
     let mut scaffolds = HashSet::new();
     let mut location = Point::new(0, 0);
@@ -50,7 +50,6 @@ fn actions_len(actions: &[char]) -> u32 {
         - 1
 }
 
-#[allow(dead_code)]
 fn format_actions(actions: &[char]) -> Vec<Value> {
     let mut output = Vec::new();
     for ch in actions {
@@ -116,7 +115,6 @@ fn find_routines_rec(
     None
 }
 
-#[allow(dead_code)]
 fn find_routines(
     scaffolds: &HashSet<Point>,
     vacum_robot_position: Point,
@@ -168,39 +166,63 @@ fn get_actions(
     actions
 }
 
+fn map_from_output(output: &[Value]) -> Vec<u8> {
+    output.iter().map(|n| u8::try_from(*n).unwrap()).collect()
+}
+
+/// Sum of the alignment parameters of the scaffold intersections
+fn part_1(mut program: Program) -> i32 {
+    let (scaffolds, _) = get_state(&map_from_output(&program.run(&[]).unwrap()));
+    scaffolds
+        .iter()
+        .filter_map(|scaffold| {
+            if scaffold.iter_nearby().all(|p| scaffolds.contains(&p)) {
+                Some(scaffold.x * scaffold.y)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+/// Dust collected by the vacuum robot after visiting every part of the scaffold
+fn part_2(mut program: Program) -> Value {
+    let (scaffolds, (vacum_robot_position, vacum_robot_direction)) =
+        get_state(&map_from_output(&program.clone().run(&[]).unwrap()));
+
+    let (main_routine, routines) =
+        find_routines(&scaffolds, vacum_robot_position, vacum_robot_direction).unwrap();
+
+    program.write_memory(0, 2);
+
+    program.run(&format_actions(&main_routine));
+    for routine in routines {
+        program.run(&format_actions(&routine));
+    }
+
+    let output = program
+        .run(&[Value::from(b'n'), Value::from(b'\n')])
+        .unwrap();
+    output.last().cloned().unwrap()
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(Program::from_input(input)).to_string(),
+    part_2: Some(|input| part_2(Program::from_input(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::intcode::Program;
     use crate::util;
-    use std::convert::TryFrom;
 
     fn get_program() -> Program {
-        util::input(17).next().expect("No input").parse().unwrap()
-    }
-
-    fn map_from_output(output: &[Value]) -> Vec<u8> {
-        output.iter().map(|n| u8::try_from(*n).unwrap()).collect()
+        Program::from_input(util::input(17))
     }
 
     #[test]
-    fn part_1() {
-        let (scaffolds, _) = get_state(&map_from_output(&get_program().run(&[]).unwrap()));
-        assert_eq!(
-            Some(
-                scaffolds
-                    .iter()
-                    .filter_map(|scaffold| {
-                        if scaffold.iter_nearby().all(|p| scaffolds.contains(&p)) {
-                            Some(scaffold.x * scaffold.y)
-                        } else {
-                            None
-                        }
-                    })
-                    .sum::<i32>()
-            ),
-            util::answer(17, 1)
-        );
+    fn part_1_test() {
+        assert_eq!(Some(part_1(get_program())), util::answer(17, 1));
     }
 
     #[test]
@@ -248,25 +270,7 @@ mod tests {
     }
 
     #[test]
-    fn part_2() {
-        let mut program = get_program();
-
-        let (scaffolds, (vacum_robot_position, vacum_robot_direction)) =
-            get_state(&map_from_output(&program.clone().run(&[]).unwrap()));
-
-        let (main_routine, routines) =
-            find_routines(&scaffolds, vacum_robot_position, vacum_robot_direction).unwrap();
-
-        program.write_memory(0, 2);
-
-        program.run(&format_actions(&main_routine));
-        for routine in routines {
-            program.run(&format_actions(&routine));
-        }
-
-        let output = program
-            .run(&[Value::from(b'n'), Value::from(b'\n')])
-            .unwrap();
-        assert_eq!(output.last().cloned(), util::answer(17, 2));
+    fn part_2_test() {
+        assert_eq!(Some(part_2(get_program())), util::answer(17, 2));
     }
 }
@@ -1,11 +1,11 @@
 use crate::point::{Direction, Point};
+use aoc::runner::Solution;
 use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 
 type Map = Vec<Vec<char>>;
 type MapSlice = [Vec<char>];
 
-#[allow(dead_code)]
 fn parse_map<S: AsRef<str>, I: Iterator<Item = S>>(iterator: I) -> (Map, Vec<Point>) {
     let mut positions = Vec::new();
     let mut y = 0;
@@ -195,7 +195,6 @@ fn compute_costs(
         .collect()
 }
 
-#[allow(dead_code)]
 fn find_shortest_path(map: &MapSlice, position: Point) -> Option<u32> {
     let mut keys = Vec::new();
     discover_keys_and_doors(map, position, &mut HashSet::new(), &[], &mut keys);
@@ -217,7 +216,6 @@ fn usize_to_char(n: usize) -> char {
     (b'0' + u8::try_from(n).unwrap()) as char
 }
 
-#[allow(dead_code)]
 fn find_shortest_path_multi_position(map: &MapSlice, positions: &[Point]) -> Option<u32> {
     let mut costs: HashMap<char, HashMap<char, u32>> = HashMap::new();
 
@@ -249,6 +247,34 @@ fn find_shortest_path_multi_position(map: &MapSlice, positions: &[Point]) -> Opt
     )
 }
 
+fn part_1(input: impl Iterator<Item = String>) -> u32 {
+    let (map, positions) = parse_map(input);
+    find_shortest_path(&map, positions[0]).expect("No path")
+}
+
+/// Fewest steps to collect all the keys, after splitting the map in four vaults
+fn part_2(input: impl Iterator<Item = String>) -> u32 {
+    let (mut map, positions) = parse_map(input);
+    let center = positions[0];
+    map[usize::try_from(center.y).unwrap()][usize::try_from(center.x).unwrap()] = '#';
+    for direction in Direction::iter() {
+        let p = center.walk(direction);
+        map[usize::try_from(p.y).unwrap()][usize::try_from(p.x).unwrap()] = '#';
+    }
+    let positions = [
+        center.walk(Direction::East).walk(Direction::South),
+        center.walk(Direction::East).walk(Direction::North),
+        center.walk(Direction::West).walk(Direction::South),
+        center.walk(Direction::West).walk(Direction::North),
+    ];
+    find_shortest_path_multi_position(&map, &positions).expect("No path")
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,10 +355,8 @@ mod tests {
     }
 
     #[test]
-    fn part_1() {
-        let (map, positions) = parse_map(util::input(18));
-
-        assert_eq!(find_shortest_path(&map, positions[0]), util::answer(18, 1));
+    fn part_1_test() {
+        assert_eq!(Some(part_1(util::input(18))), util::answer(18, 1));
     }
 
     #[test]
@@ -371,23 +395,7 @@ mod tests {
     }
 
     #[test]
-    fn part_2() {
-        let (mut map, positions) = parse_map(util::input(18));
-        let center = positions[0];
-        map[usize::try_from(center.y).unwrap()][usize::try_from(center.x).unwrap()] = '#';
-        for direction in Direction::iter() {
-            let p = center.walk(direction);
-            map[usize::try_from(p.y).unwrap()][usize::try_from(p.x).unwrap()] = '#';
-        }
-        let positions = [
-            center.walk(Direction::East).walk(Direction::South),
-            center.walk(Direction::East).walk(Direction::North),
-            center.walk(Direction::West).walk(Direction::South),
-            center.walk(Direction::West).walk(Direction::North),
-        ];
-        assert_eq!(
-            find_shortest_path_multi_position(&map, &positions),
-            util::answer(18, 2)
-        );
+    fn part_2_test() {
+        assert_eq!(Some(part_2(util::input(18))), util::answer(18, 2));
     }
 }
@@ -1,9 +1,13 @@
-#[cfg(test)]
-mod tests {
+pub(crate) use solution::SOLUTION;
+
+mod solution {
     use crate::intcode::{Program, Value};
+    #[cfg(test)]
     use crate::util;
+    use aoc::runner::Solution;
     use itertools::iproduct;
 
+    #[cfg(test)]
     fn get_program() -> Program {
         util::input(19).next().expect("No input").parse().unwrap()
     }
@@ -12,31 +16,39 @@ mod tests {
         program.clone().run(&[x, y]).get_output()[0] == 1
     }
 
-    #[test]
-    fn part_1() {
-        let program = get_program();
-        assert_eq!(
-            Some(
-                iproduct!(0..50, 0..50)
-                    .filter(|(x, y)| is_in_beam(&program, *x, *y))
-                    .count()
-            ),
-            util::answer(19, 1)
-        );
+    /// Number of points affected by the beam in the 50x50 area closest to the emitter
+    fn part_1(program: &Program) -> usize {
+        iproduct!(0..50, 0..50)
+            .filter(|(x, y)| is_in_beam(program, *x, *y))
+            .count()
     }
 
-    #[test]
+    /// Position of the closest 100x100 square fitting in the beam, as `x * 10000 + y`
     #[allow(clippy::maybe_infinite_iter)]
-    fn part_2() {
-        let program = get_program();
+    fn part_2(program: &Program) -> Value {
         let ship_size = 100;
         let mut x = 0;
         let mut y = 0;
-        while !is_in_beam(&program, x + ship_size - 1, y - ship_size + 1) {
+        while !is_in_beam(program, x + ship_size - 1, y - ship_size + 1) {
             y += 1;
-            x = (x..).find(|x| is_in_beam(&program, *x, y)).unwrap();
+            x = (x..).find(|x| is_in_beam(program, *x, y)).unwrap();
         }
         y -= ship_size - 1;
-        assert_eq!(Some(x * 10_000 + y), util::answer(19, 2));
+        x * 10_000 + y
+    }
+
+    pub(crate) const SOLUTION: Solution = Solution {
+        part_1: |input| part_1(&Program::from_input(input)).to_string(),
+        part_2: Some(|input| part_2(&Program::from_input(input)).to_string()),
+    };
+
+    #[test]
+    fn part_1_test() {
+        assert_eq!(Some(part_1(&get_program())), util::answer(19, 1));
+    }
+
+    #[test]
+    fn part_2_test() {
+        assert_eq!(Some(part_2(&get_program())), util::answer(19, 2));
     }
 }
@@ -1,7 +1,10 @@
-#[cfg(test)]
-mod tests {
+pub(crate) use solution::SOLUTION;
+
+mod solution {
     use crate::point::Point;
+    #[cfg(test)]
     use crate::util;
+    use aoc::runner::Solution;
     use std::collections::HashMap;
     use std::convert::TryInto;
 
@@ -258,6 +261,19 @@ mod tests {
         )
     }
 
+    pub(crate) const SOLUTION: Solution = Solution {
+        part_1: |input| {
+            find_shortest_path(&parse_map(input))
+                .expect("No path found")
+                .to_string()
+        },
+        part_2: Some(|input| {
+            find_shortest_path2(&parse_map(input))
+                .expect("No path found")
+                .to_string()
+        }),
+    };
+
     #[test]
     fn test_1_1() {
         let map = parse_map(
@@ -1,8 +1,12 @@
-#[cfg(test)]
-mod tests {
+pub(crate) use solution::SOLUTION;
+
+mod solution {
     use crate::intcode::{Program, ProgramResult, Value};
+    #[cfg(test)]
     use crate::util;
+    use aoc::runner::Solution;
 
+    #[cfg(test)]
     fn get_program() -> Program {
         util::input(21).next().expect("No input").parse().unwrap()
     }
@@ -17,10 +21,9 @@ mod tests {
         }
     }
 
-    #[test]
-    fn part_1() {
-        let mut program = get_program();
-
+    /// Hull damage reported by the droid walking with a springscript program, which only senses
+    /// the next four tiles
+    fn walk(mut program: Program) -> Option<Value> {
         // !A | !B | !C -> J
         program.run_str("NOT A J\n");
         program.run_str("NOT B T\n");
@@ -32,13 +35,12 @@ mod tests {
         program.run_str("AND D J\n");
 
         let result = program.run_str("WALK\n");
-        assert_eq!(get_damages(&result), util::answer(21, 1));
+        get_damages(&result)
     }
 
-    #[test]
-    fn part_2() {
-        let mut program = get_program();
-
+    /// Hull damage reported by the droid running with a springscript program, which senses the
+    /// next nine tiles
+    fn run(mut program: Program) -> Option<Value> {
         // Goal:
         // !(A & B & C) & D & (H | (E & (F | I))) -> J
 
@@ -65,7 +67,29 @@ mod tests {
         program.run_str("AND T J\n");
 
         let result = program.run_str("RUN\n");
+        get_damages(&result)
+    }
 
-        assert_eq!(get_damages(&result), util::answer(21, 2));
+    pub(crate) const SOLUTION: Solution = Solution {
+        part_1: |input| {
+            walk(Program::from_input(input))
+                .expect("The droid fell")
+                .to_string()
+        },
+        part_2: Some(|input| {
+            run(Program::from_input(input))
+                .expect("The droid fell")
+                .to_string()
+        }),
+    };
+
+    #[test]
+    fn part_1() {
+        assert_eq!(walk(get_program()), util::answer(21, 1));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(run(get_program()), util::answer(21, 2));
     }
 }
@@ -1,6 +1,9 @@
-#[cfg(test)]
-mod tests {
+pub(crate) use solution::SOLUTION;
+
+mod solution {
+    #[cfg(test)]
     use crate::util;
+    use aoc::runner::Solution;
     use std::convert::TryFrom;
 
     #[derive(Debug, Copy, Clone)]
@@ -57,6 +60,7 @@ mod tests {
         deck
     }
 
+    #[cfg(test)]
     fn get_card_position(instructions: &[Instruction], card_count: usize, card: usize) -> usize {
         let mut position = card;
         for instruction in instructions.iter().rev() {
@@ -80,6 +84,18 @@ mod tests {
         position
     }
 
+    pub(crate) const SOLUTION: Solution = Solution {
+        part_1: |input| {
+            shuffle_deck(input, 10007)
+                .into_iter()
+                .position(|card| card == 2019)
+                .expect("Card not found")
+                .to_string()
+        },
+        // See the `part_2` test
+        part_2: None,
+    };
+
     #[test]
     fn test_1_1() {
         assert_eq!(
@@ -1,19 +1,22 @@
-#[cfg(test)]
-mod tests {
+pub(crate) use solution::SOLUTION;
+
+mod solution {
     use crate::intcode::{Program, Value};
+    #[cfg(test)]
     use crate::util;
+    use aoc::runner::Solution;
     use itertools::Itertools;
     use std::collections::VecDeque;
     use std::convert::TryFrom;
 
     type Computer = (Program, VecDeque<(Value, Value)>);
 
+    #[cfg(test)]
     fn get_program() -> Program {
         util::input(23).next().expect("No input").parse().unwrap()
     }
 
-    fn get_computers() -> Vec<Computer> {
-        let program = get_program();
+    fn get_computers(program: &Program) -> Vec<Computer> {
         (0..50)
             .map(|address| {
                 let mut program = program.clone();
@@ -36,9 +39,9 @@ mod tests {
         .collect()
     }
 
-    #[test]
-    fn part_1() {
-        let mut computers = get_computers();
+    /// Y value of the first packet sent to the address 255
+    fn first_nat_packet(program: &Program) -> Value {
+        let mut computers = get_computers(program);
 
         loop {
             for address in 0..computers.len() {
@@ -46,8 +49,7 @@ mod tests {
 
                 for (destination, packet) in output {
                     if destination == 255 {
-                        assert_eq!(Some(packet.1), util::answer(23, 1));
-                        return;
+                        return packet.1;
                     }
                     computers[destination].1.push_back(packet);
                 }
@@ -55,9 +57,9 @@ mod tests {
         }
     }
 
-    #[test]
-    fn part_2() {
-        let mut computers = get_computers();
+    /// First Y value delivered by the NAT to the computer 0 twice in a row
+    fn first_nat_repeat(program: &Program) -> Value {
+        let mut computers = get_computers(program);
 
         let mut nat = (0, 0);
         let mut previously_delivered = 0;
@@ -77,12 +79,26 @@ mod tests {
 
             if computers.iter().all(|(_, queue)| queue.is_empty()) {
                 if previously_delivered == nat.1 {
-                    assert_eq!(Some(previously_delivered), util::answer(23, 2));
-                    return;
+                    return previously_delivered;
                 }
                 computers[0].1.push_back(nat);
                 previously_delivered = nat.1;
             }
         }
     }
+
+    pub(crate) const SOLUTION: Solution = Solution {
+        part_1: |input| first_nat_packet(&Program::from_input(input)).to_string(),
+        part_2: Some(|input| first_nat_repeat(&Program::from_input(input)).to_string()),
+    };
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Some(first_nat_packet(&get_program())), util::answer(23, 1));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Some(first_nat_repeat(&get_program())), util::answer(23, 2));
+    }
 }
@@ -1,8 +1,11 @@
-#[cfg(test)]
-mod tests {
+pub(crate) use solution::SOLUTION;
+
+mod solution {
+    #[cfg(test)]
     use crate::util;
+    use aoc::cycle;
+    use aoc::runner::Solution;
     use itertools::{iproduct, Itertools};
-    use std::collections::HashSet;
 
     const GRID_SIZE: u32 = 5;
     type Map = u32;
@@ -88,18 +91,18 @@ mod tests {
         );
     }
 
+    /// Biodiversity rating of the first layout appearing twice
+    fn first_repeated_layout(map: Map) -> Map {
+        let cycle = cycle::find_cycle(map, |&map| step(map));
+        (0..cycle.start).fold(map, |map, _| step(map))
+    }
+
     #[test]
     fn part_1() {
-        let mut map = parse_map(util::input(24));
-
-        let mut layouts = HashSet::new();
-
-        while !layouts.contains(&map) {
-            layouts.insert(map);
-            map = step(map);
-        }
-
-        assert_eq!(Some(map), util::answer(24, 1));
+        assert_eq!(
+            Some(first_repeated_layout(parse_map(util::input(24)))),
+            util::answer(24, 1)
+        );
     }
 
     fn step_rec(levels: &[Map]) -> Vec<Map> {
@@ -197,16 +200,25 @@ mod tests {
         );
     }
 
-    #[test]
-    fn part_2() {
-        let map = parse_map(util::input(24));
-
+    /// Number of bugs after 200 minutes in the recursive grids
+    fn recursive_bug_count(map: Map) -> u32 {
         let mut levels = vec![0, 0, map, 0, 0];
         for _ in 0..200 {
             levels = step_rec(&levels);
         }
-        let result: u32 = levels.iter().map(|level| level.count_ones()).sum();
+        levels.iter().map(|level| level.count_ones()).sum()
+    }
+
+    pub(crate) const SOLUTION: Solution = Solution {
+        part_1: |input| first_repeated_layout(parse_map(input)).to_string(),
+        part_2: Some(|input| recursive_bug_count(parse_map(input)).to_string()),
+    };
 
-        assert_eq!(Some(result), util::answer(24, 2));
+    #[test]
+    fn part_2() {
+        assert_eq!(
+            Some(recursive_bug_count(parse_map(util::input(24)))),
+            util::answer(24, 2)
+        );
     }
 }
@@ -160,6 +160,17 @@ impl std::str::FromStr for Program {
     }
 }
 
+impl Program {
+    /// Program written on the first line of a puzzle input
+    pub fn from_input(mut input: impl Iterator<Item = String>) -> Self {
+        input
+            .next()
+            .expect("No input")
+            .parse()
+            .expect("Invalid program")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
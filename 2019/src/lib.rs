@@ -24,6 +24,7 @@ mod day23;
 mod day24;
 mod day25;
 mod intcode;
-mod ocr;
-mod point;
+pub mod runner;
 mod util;
+
+use aoc::point;
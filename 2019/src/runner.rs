@@ -0,0 +1,34 @@
+use crate::util::YEAR;
+use aoc::runner::Solution;
+
+// Day 25 is solved by exploring the ship interactively, see its test
+const SOLUTIONS: &[(u8, Solution)] = &[
+    (1, crate::day01::SOLUTION),
+    (2, crate::day02::SOLUTION),
+    (3, crate::day03::SOLUTION),
+    (4, crate::day04::SOLUTION),
+    (5, crate::day05::SOLUTION),
+    (6, crate::day06::SOLUTION),
+    (7, crate::day07::SOLUTION),
+    (8, crate::day08::SOLUTION),
+    (9, crate::day09::SOLUTION),
+    (10, crate::day10::SOLUTION),
+    (11, crate::day11::SOLUTION),
+    (12, crate::day12::SOLUTION),
+    (13, crate::day13::SOLUTION),
+    (14, crate::day14::SOLUTION),
+    (15, crate::day15::SOLUTION),
+    (16, crate::day16::SOLUTION),
+    (17, crate::day17::SOLUTION),
+    (18, crate::day18::SOLUTION),
+    (19, crate::day19::SOLUTION),
+    (20, crate::day20::SOLUTION),
+    (21, crate::day21::SOLUTION),
+    (22, crate::day22::SOLUTION),
+    (23, crate::day23::SOLUTION),
+    (24, crate::day24::SOLUTION),
+];
+
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    aoc::runner::run(YEAR, SOLUTIONS, args)
+}
@@ -0,0 +1,6 @@
+fn main() {
+    if let Err(message) = aoc_2020::runner::run(std::env::args().skip(1)) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
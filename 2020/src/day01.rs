@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use itertools::Itertools;
 
 #[allow(dead_code)]
@@ -28,6 +29,11 @@ fn part_2<T: Iterator<Item = i32>>(expenses: T) -> i32 {
         .unwrap()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input.flat_map(|line| line.parse())).to_string(),
+    part_2: Some(|input| part_2(input.flat_map(|line| line.parse())).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,11 @@
+use aoc::runner::Solution;
+
 fn parse<T: Iterator<Item = String>, F: Fn(usize, usize, char, &str) -> bool>(
     lines: T,
     validator: F,
 ) -> usize {
     lines
         .filter(|line| {
-            dbg!(&line);
             let mut iter = line.split(|ch| ch == ' ' || ch == ':' || ch == '-');
             let min: usize = iter.next().unwrap().parse().unwrap();
             let max: usize = iter.next().unwrap().parse().unwrap();
@@ -37,6 +38,11 @@ fn part_2<T: Iterator<Item = String>>(lines: T) -> usize {
     })
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
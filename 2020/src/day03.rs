@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 fn run_slope<S: AsRef<str>, T: Iterator<Item = S>>(lines: T, dx: usize, dy: usize) -> usize {
     lines
         .step_by(dy)
@@ -25,6 +27,11 @@ fn part_2<T: Iterator<Item = String>>(lines: T) -> usize {
         * run_slope(lines.iter(), 1, 2)
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 fn validate_passports<F: Fn(&str, &str) -> bool, T: Iterator<Item = String>>(
     lines: T,
     is_required_valid_field: F,
@@ -95,6 +97,11 @@ fn part_2<T: Iterator<Item = String>>(lines: T) -> usize {
     })
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 fn decode_id(s: &str) -> u32 {
     s.chars().fold(0, |total, ch| {
         (total << 1)
@@ -24,6 +26,11 @@ fn part_2<T: Iterator<Item = String>>(lines: T) -> Option<u32> {
         .map(|couple| couple[0] + 1)
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).unwrap().to_string(),
+    part_2: Some(|input| part_2(input).unwrap().to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,46 +1,40 @@
+use aoc::parse;
+use aoc::runner::Solution;
 use std::collections::HashSet;
 
 #[allow(dead_code)]
 fn part_1<T: Iterator<Item = String>>(lines: T) -> usize {
-    lines
-        .chain(std::iter::once(String::new()))
-        .scan(HashSet::new(), |group, line| {
-            Some(if line.is_empty() {
-                Some(std::mem::replace(group, HashSet::new()))
-            } else {
-                for ch in line.chars() {
-                    group.insert(ch);
-                }
-                None
-            })
+    parse::groups(lines)
+        .into_iter()
+        .map(|(_, group)| {
+            group
+                .iter()
+                .flat_map(|line| line.chars())
+                .collect::<HashSet<_>>()
+                .len()
         })
-        .flatten()
-        .map(|group| group.len())
         .sum()
 }
 
 #[allow(dead_code)]
 fn part_2<T: Iterator<Item = String>>(lines: T) -> usize {
-    lines
-        .chain(std::iter::once(String::new()))
-        .scan(Option::<HashSet<char>>::None, |group, line| {
-            Some(if line.is_empty() {
-                group.take()
-            } else {
-                let line_group: HashSet<_> = line.chars().collect();
-                if let Some(group) = group {
-                    group.retain(|ch| line_group.contains(ch));
-                } else {
-                    *group = Some(line_group);
-                }
-                None
-            })
+    parse::groups(lines)
+        .into_iter()
+        .map(|(_, group)| {
+            let mut answers: HashSet<_> = group[0].chars().collect();
+            for line in &group[1..] {
+                answers.retain(|ch| line.contains(*ch));
+            }
+            answers.len()
         })
-        .flatten()
-        .map(|group| group.len())
         .sum()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use itertools::Itertools;
@@ -74,6 +75,11 @@ fn parse_rules<T: Iterator<Item = String>>(lines: T) -> HashMap<String, HashMap<
         .collect()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,4 +5,5 @@ mod day04;
 mod day05;
 mod day06;
 mod day07;
+pub mod runner;
 mod util;
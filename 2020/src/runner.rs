@@ -0,0 +1,16 @@
+use crate::util::YEAR;
+use aoc::runner::Solution;
+
+const SOLUTIONS: &[(u8, Solution)] = &[
+    (1, crate::day01::SOLUTION),
+    (2, crate::day02::SOLUTION),
+    (3, crate::day03::SOLUTION),
+    (4, crate::day04::SOLUTION),
+    (5, crate::day05::SOLUTION),
+    (6, crate::day06::SOLUTION),
+    (7, crate::day07::SOLUTION),
+];
+
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    aoc::runner::run(YEAR, SOLUTIONS, args)
+}
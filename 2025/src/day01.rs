@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 #[allow(dead_code)]
 fn part_1<T: Iterator<Item = i32>>(moves: T) -> i32 {
     let mut position = 50i32;
@@ -41,6 +43,11 @@ fn parse_line(line: impl AsRef<str>) -> i32 {
     }
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input.map(parse_line)).to_string(),
+    part_2: Some(|input| part_2(input.map(parse_line)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,26 +1,27 @@
+use aoc::interval_set::IntervalSet;
+use aoc::runner::Solution;
 use std::collections::HashSet;
-
-type Range = (u64, u64);
+use std::ops::Range;
 
 #[allow(dead_code)]
-fn part_1(ranges: &[Range]) -> u64 {
+fn part_1(ranges: &IntervalSet<u64>) -> u64 {
     let repeatitions = 2;
 
     let mut sum: u64 = 0;
-    for range in ranges {
-        sum += count_repeated_in_range(*range, repeatitions, &mut HashSet::new());
+    for range in ranges.iter() {
+        sum += count_repeated_in_range(range, repeatitions, &mut HashSet::new());
     }
 
     sum
 }
 
 #[allow(dead_code)]
-fn part_2(ranges: &[Range]) -> u64 {
+fn part_2(ranges: &IntervalSet<u64>) -> u64 {
     let mut sum: u64 = 0;
-    for range in &ranges[0..] {
+    for range in ranges.iter() {
         let mut previous: HashSet<u64> = HashSet::new();
         for repeatitions in 2..=10 {
-            sum += count_repeated_in_range(*range, repeatitions, &mut previous);
+            sum += count_repeated_in_range(range.clone(), repeatitions, &mut previous);
         }
     }
 
@@ -28,7 +29,7 @@ fn part_2(ranges: &[Range]) -> u64 {
 }
 
 fn count_repeated_in_range(
-    (start, end): Range,
+    Range { start, end }: Range<u64>,
     repetitions: u32,
     previous: &mut HashSet<u64>,
 ) -> u64 {
@@ -43,7 +44,7 @@ fn count_repeated_in_range(
             repeated_part * 10u64.pow(repeated_part_digits * pow) + acc
         });
 
-        if repeated > end {
+        if repeated >= end {
             break;
         }
 
@@ -58,18 +59,23 @@ fn count_repeated_in_range(
 }
 
 #[allow(dead_code)]
-fn parse_ranges(lines: impl Iterator<Item = String>) -> Vec<Range> {
+fn parse_ranges(lines: impl Iterator<Item = String>) -> IntervalSet<u64> {
     let content = lines.collect::<String>();
 
     content
         .split(',')
         .map(|range| {
             let (start, end) = range.split_once('-').unwrap();
-            (start.parse().unwrap(), end.parse().unwrap())
+            start.parse().unwrap()..end.parse::<u64>().unwrap() + 1
         })
         .collect()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(&parse_ranges(input)).to_string(),
+    part_2: Some(|input| part_2(&parse_ranges(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
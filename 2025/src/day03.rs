@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 #[allow(dead_code)]
 fn part_1(banks: impl Iterator<Item = Vec<u8>>) -> u64 {
     let mut sum = 0;
@@ -46,6 +48,11 @@ fn parse_bank(line: String) -> Vec<u8> {
         .collect()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input.map(parse_bank)).to_string(),
+    part_2: Some(|input| part_2(input.map(parse_bank)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
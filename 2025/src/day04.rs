@@ -1,3 +1,4 @@
+use aoc::runner::Solution;
 use std::ops::Range;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -71,6 +72,11 @@ fn can_remove(grid: &Grid, x: usize, y: usize) -> bool {
     occupied_space < 5
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(parse_grid(input)).to_string(),
+    part_2: Some(|input| part_2(parse_grid(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,31 +1,21 @@
-use std::ops::Range;
+use aoc::interval_set::IntervalSet;
+use aoc::runner::Solution;
 
 fn part_1(database: Database) -> usize {
     database
         .ids
         .iter()
-        .filter(|id| database.ranges.iter().any(|range| range.contains(id)))
+        .filter(|id| database.ranges.contains(id))
         .count()
 }
 
-fn part_2(mut database: Database) -> u64 {
-    database.ranges.sort_by_key(|range| range.start);
-
-    let mut min = 0;
-    database
-        .ranges
-        .iter()
-        .map(|range| {
-            let adjusted_range = range.start.max(min)..range.end.max(min);
-            min = adjusted_range.end;
-            adjusted_range.end - adjusted_range.start
-        })
-        .sum()
+fn part_2(database: Database) -> u64 {
+    database.ranges.covered_length()
 }
 
 #[derive(Debug, Default)]
 struct Database {
-    ranges: Vec<Range<u64>>,
+    ranges: IntervalSet<u64>,
     ids: Vec<u64>,
 }
 
@@ -36,7 +26,7 @@ fn parse_database(lines: impl Iterator<Item = String>) -> Database {
         if let Some((start, end)) = line.split_once('-') {
             let start = start.parse().unwrap();
             let end: u64 = end.parse().unwrap();
-            database.ranges.push(start..(end + 1));
+            database.ranges.insert(start..(end + 1));
         } else if let Ok(id) = line.parse() {
             database.ids.push(id);
         }
@@ -45,6 +35,11 @@ fn parse_database(lines: impl Iterator<Item = String>) -> Database {
     database
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(parse_database(input)).to_string(),
+    part_2: Some(|input| part_2(parse_database(input)).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
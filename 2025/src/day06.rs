@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 fn part_1(lines: impl Iterator<Item = String>) -> u64 {
     let mut rows: Vec<Vec<u64>> = Vec::new();
     let mut operands: Vec<char> = Vec::new();
@@ -63,6 +65,11 @@ fn compute(op: char, numbers_it: impl Iterator<Item = u64>) -> u64 {
     }
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use aoc::runner::Solution;
+
 fn part_1(mut lines: impl Iterator<Item = String>) -> u64 {
     let mut beams: Vec<bool> = lines.next().unwrap().chars().map(|c| c == 'S').collect();
     let mut count = 0;
@@ -46,6 +48,11 @@ fn part_2(mut lines: impl Iterator<Item = String>) -> u64 {
     timelines.iter().sum()
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,61 +1,32 @@
+use aoc::runner::Solution;
+use aoc::union_find::UnionFind;
+
 type Box = (i64, i64, i64);
 
 fn part_1(lines: impl Iterator<Item = String>, max: usize) -> u32 {
     let (boxes, distances) = parse_boxes_and_sorted_distances(lines);
 
-    let mut circuits: Vec<usize> = boxes.iter().enumerate().map(|(i, _)| i).collect();
+    let mut circuits = UnionFind::new(boxes.len());
 
     for (i, j, _) in distances.into_iter().take(max) {
-        let connected_circuit = circuits[i].min(circuits[j]);
-        let removed_circuit = circuits[i].max(circuits[j]);
-        for circuit in circuits.iter_mut() {
-            if circuit == &removed_circuit {
-                *circuit = connected_circuit;
-            }
-        }
+        circuits.union(i, j);
     }
 
-    circuits.sort();
-
-    let mut circuits_counts: Vec<u32> = circuits
+    circuits
+        .largest_component_sizes(3)
         .iter()
-        .scan((0, 0u32), |(previous_circuit, count), circuit| {
-            if *previous_circuit == *circuit {
-                *count += 1;
-                Some(None)
-            } else {
-                let result = Some(*count);
-                *previous_circuit = *circuit;
-                *count = 1;
-                Some(result)
-            }
-        })
-        .flatten()
-        .collect();
-
-    circuits_counts.sort();
-
-    circuits_counts.iter().rev().take(3).product::<u32>()
+        .map(|size| *size as u32)
+        .product()
 }
 
 fn part_2(lines: impl Iterator<Item = String>) -> u32 {
     let (boxes, distances) = parse_boxes_and_sorted_distances(lines);
 
-    let mut circuits: Vec<usize> = boxes.iter().enumerate().map(|(i, _)| i).collect();
+    let mut circuits = UnionFind::new(boxes.len());
 
     for (i, j, _) in distances.into_iter() {
-        let connected_circuit = circuits[i].min(circuits[j]);
-        let removed_circuit = circuits[i].max(circuits[j]);
-        let mut all_0 = true;
-        for circuit in circuits.iter_mut() {
-            if circuit == &removed_circuit {
-                *circuit = connected_circuit;
-            }
-            if *circuit != 0 {
-                all_0 = false;
-            }
-        }
-        if all_0 {
+        circuits.union(i, j);
+        if let Some((i, j)) = circuits.final_union() {
             return (boxes[i].0 * boxes[j].0) as u32;
         }
     }
@@ -87,6 +58,11 @@ fn parse_boxes_and_sorted_distances(
     (boxes, distances)
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input, 1000).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use aoc::parse;
+use aoc::runner::Solution;
 use std::iter;
 
 use itertools::Itertools as _;
@@ -35,11 +37,19 @@ fn part_2(lines: impl Iterator<Item = String>) -> i64 {
 
 fn parse_points(lines: impl Iterator<Item = String>) -> Vec<Point> {
     lines
-        .map(|s| {
-            let (x, y) = s.split_once(',').unwrap();
-            (x.parse().unwrap(), y.parse().unwrap())
-        })
-        .collect()
+        .enumerate()
+        .map(
+            |(index, line)| match parse::integers(index + 1, &line)?[..] {
+                [x, y] => Ok((x, y)),
+                _ => Err(parse::Error {
+                    line: index + 1,
+                    column: 1,
+                    kind: parse::ErrorKind::Expected("two coordinates"),
+                }),
+            },
+        )
+        .collect::<parse::Result<_>>()
+        .unwrap_or_else(|error| panic!("Invalid input: {}", error))
 }
 
 fn is_only_red_and_green(rectangle: Rectangle, points: &[Point]) -> bool {
@@ -66,6 +76,11 @@ fn is_only_red_and_green(rectangle: Rectangle, points: &[Point]) -> bool {
         })
 }
 
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -9,5 +9,5 @@ mod day06;
 mod day07;
 mod day08;
 mod day09;
-//mod point;
+pub mod runner;
 mod util;
@@ -1,67 +0,0 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
-}
-
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    pub fn relative_to(self, origin: Self) -> Self {
-        Self::new(self.x - origin.x, self.y - origin.y)
-    }
-
-    pub fn distance(self, other: Self) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-
-    pub fn walk(self, direction: Direction) -> Self {
-        match direction {
-            Direction::North => Self::new(self.x, self.y - 1),
-            Direction::South => Self::new(self.x, self.y + 1),
-            Direction::East => Self::new(self.x + 1, self.y),
-            Direction::West => Self::new(self.x - 1, self.y),
-        }
-    }
-
-    #[allow(dead_code)]
-    pub fn iter_nearby(self) -> impl Iterator<Item = Self> {
-        Direction::iter().map(move |d| self.walk(d))
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Direction {
-    North,
-    East,
-    West,
-    South,
-}
-
-impl Direction {
-    pub fn iter() -> impl Iterator<Item = Self> {
-        [Self::North, Self::East, Self::West, Self::South]
-            .iter()
-            .cloned()
-    }
-
-    pub fn left(self) -> Self {
-        match self {
-            Self::North => Self::West,
-            Self::West => Self::South,
-            Self::South => Self::East,
-            Self::East => Self::North,
-        }
-    }
-
-    pub fn right(self) -> Self {
-        match self {
-            Self::North => Self::East,
-            Self::East => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::North,
-        }
-    }
-}
@@ -0,0 +1,140 @@
+use crate::util::CACHE_DIR;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// An adventofcode.com account. Each account has its own inputs and answers, stored in its own
+/// sub directory of the cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Account {
+    pub name: String,
+    pub session: String,
+}
+
+impl Account {
+    /// Account named after a hash of its session, so the session doesn't leak in the cache path
+    pub fn from_session(session: &str) -> Self {
+        let hash = Sha256::digest(session.as_bytes());
+        Self {
+            name: hash[..4]
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn cache_dir(&self) -> PathBuf {
+        Path::new(CACHE_DIR).join(&self.name)
+    }
+}
+
+/// Path of the file registering accounts: `AOC_ACCOUNTS` if set, `~/.aoc_accounts` otherwise
+pub fn accounts_path() -> PathBuf {
+    std::env::var_os("AOC_ACCOUNTS")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".aoc_accounts")))
+        .unwrap_or_else(|| PathBuf::from(".aoc_accounts"))
+}
+
+/// Registered accounts, one "name session" per line
+pub fn load_accounts(path: &Path) -> io::Result<Vec<Account>> {
+    let content = match fs::read_to_string(path) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        content => content?,
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (name, session) = line.trim().split_once(' ')?;
+            Some(Account {
+                name: name.to_string(),
+                session: session.trim().to_string(),
+            })
+        })
+        .collect())
+}
+
+pub fn save_accounts(path: &Path, accounts: &[Account]) -> io::Result<()> {
+    let content: String = accounts
+        .iter()
+        .map(|account| format!("{} {}\n", account.name, account.session))
+        .collect();
+    // Sessions give access to the accounts, keep them private
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(content.as_bytes())
+}
+
+/// Add an account to the registry, replacing any account with the same name
+pub fn register(path: &Path, account: Account) -> io::Result<()> {
+    let mut accounts = load_accounts(path)?;
+    accounts.retain(|registered| registered.name != account.name);
+    accounts.push(account);
+    save_accounts(path, &accounts)
+}
+
+/// Account selected by the `AOC_PROFILE` environment variable, if any
+pub fn profile() -> Option<Account> {
+    let name = std::env::var("AOC_PROFILE").ok()?;
+    let path = accounts_path();
+    let accounts = load_accounts(&path)
+        .unwrap_or_else(|error| panic!("Failed to read {}: {}", path.display(), error));
+    let account = accounts
+        .into_iter()
+        .find(|account| account.name == name)
+        .unwrap_or_else(|| panic!("Account {} is not registered in {}", name, path.display()));
+    Some(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_test() {
+        let path = std::env::temp_dir().join(format!("aoc-accounts-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        assert!(load_accounts(&path).unwrap().is_empty());
+        let anonymous = Account::from_session("53616c7465645f5f");
+        assert_eq!(anonymous.name.len(), 8);
+        assert_eq!(anonymous, Account::from_session("53616c7465645f5f"));
+        register(&path, anonymous.clone()).unwrap();
+        register(&path, Account::from_session("other")).unwrap();
+        register(
+            &path,
+            Account {
+                name: anonymous.name.clone(),
+                session: "new".to_string(),
+            },
+        )
+        .unwrap();
+
+        let accounts = load_accounts(&path).unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[1].name, anonymous.name);
+        assert_eq!(accounts[1].session, "new");
+        assert_eq!(
+            accounts[1].cache_dir(),
+            Path::new("cache").join(&anonymous.name)
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
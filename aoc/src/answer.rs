@@ -0,0 +1,195 @@
+use crate::ocr::ocr;
+use num_bigint::BigInt;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Answer of a puzzle part, as returned by a solution or parsed from the cache. Two answers are
+/// equal when they are submitted as the same text, so an image is equal to the letters it
+/// renders.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i128),
+    /// Integers that don't fit in an `i128`
+    BigInt(BigInt),
+    String(String),
+    /// Letters rendered with '#' and ' ' (or '.'), read with OCR. Images that can't be read are
+    /// compared and printed as they are.
+    Image(String),
+}
+
+impl Answer {
+    pub fn image(image: &str) -> Self {
+        let lines: Vec<_> = image
+            .trim_matches('\n')
+            .lines()
+            .map(|line| line.replace('.', " ").trim_end().to_string())
+            .collect();
+        Self::Image(lines.join("\n"))
+    }
+
+    fn is_image(text: &str) -> bool {
+        text.trim_matches('\n').contains('\n')
+            && text.chars().all(|c| matches!(c, '#' | ' ' | '.' | '\n'))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{}", value),
+            Self::BigInt(value) => write!(f, "{}", value),
+            Self::String(value) => write!(f, "{}", value),
+            Self::Image(image) => match ocr(image) {
+                Some(letters) => write!(f, "{}", letters),
+                None => write!(f, "\n{}", image),
+            },
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::BigInt(a), Self::BigInt(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Parse an answer read from the cache, or printed by a solution
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(value) = text.parse() {
+            Self::Int(value)
+        } else if let Ok(value) = text.parse() {
+            Self::BigInt(value)
+        } else if Self::is_image(text) {
+            Self::image(text)
+        } else {
+            Self::String(text.to_string())
+        })
+    }
+}
+
+macro_rules! from_int {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Answer {
+                fn from(value: $type) -> Self {
+                    Self::Int(value.into())
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Int(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Int(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(value) => Self::Int(value),
+            Err(_) => Self::BigInt(value.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        match i128::try_from(&value) {
+            Ok(value) => Self::Int(value),
+            Err(_) => Self::BigInt(value),
+        }
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let parse = |text: &str| text.parse::<Answer>().unwrap();
+        assert!(matches!(parse("-42"), Answer::Int(-42)));
+        assert!(matches!(
+            parse("1000000000000000000000000000000000000000"),
+            Answer::BigInt(_)
+        ));
+        assert!(matches!(parse("CABDFE"), Answer::String(_)));
+        assert!(matches!(parse("#  #\n#  #"), Answer::Image(_)));
+    }
+
+    #[test]
+    fn eq_test() {
+        assert_eq!(Answer::from(42u8), "42".parse().unwrap());
+        assert_eq!(Answer::from(42usize), Answer::from("42"));
+        assert_ne!(Answer::from(42), Answer::from(43i64));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            u128::MAX.to_string().parse().unwrap()
+        );
+        assert_eq!(Answer::from(BigInt::from(7)), Answer::from(7u64));
+
+        let image = "\
+            .##..###.\n\
+            #..#.#..#\n\
+            #..#.###.\n\
+            ####.#..#\n\
+            #..#.#..#\n\
+            #..#.###.";
+        assert_eq!(Answer::image(image), "AB".parse().unwrap());
+        assert_eq!(image.parse::<Answer>().unwrap(), Answer::from("AB"));
+
+        // Unknown glyph, and a width that isn't a multiple of 5 minus 1
+        let unknown = "\
+            #.#.#..##\n\
+            .#.#..#..\n\
+            #.#.#..##\n\
+            .#.#..#..\n\
+            #.#.#..##\n\
+            .#.#..#..";
+        let answer: Answer = unknown.parse().unwrap();
+        assert!(matches!(answer, Answer::Image(_)));
+        assert_eq!(answer, Answer::image(&format!("{}\n", unknown)));
+        assert_ne!(answer, Answer::image(image));
+        assert_ne!(answer, Answer::from("AB"));
+        assert!(answer.to_string().starts_with("\n# # #  ##\n # #  #"));
+        assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer);
+    }
+}
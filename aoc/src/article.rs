@@ -0,0 +1,188 @@
+/// An example found in a puzzle description: the content of a `<pre><code>` block, and the
+/// emphasized answer following it, if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub lines: Vec<String>,
+    pub answer: Option<String>,
+}
+
+/// Parse the examples of each part of a day page. The first element contains the examples of the
+/// first part, the second element (only present once the first part is solved) contains the
+/// examples of the second part.
+pub fn parse_parts(html: &str) -> Vec<Vec<Example>> {
+    html.split("<article class=\"day-desc\">")
+        .skip(1)
+        .map(|rest| rest.split("</article>").next().unwrap_or(rest))
+        .map(parse_examples)
+        .collect()
+}
+
+/// Parse every example of a page, in order, regardless of the part they belong to
+pub fn parse_all_examples(html: &str) -> Vec<Example> {
+    parse_examples(html)
+}
+
+fn parse_examples(article: &str) -> Vec<Example> {
+    let mut examples = Vec::new();
+    let mut rest = article;
+    while let Some((_, block_start)) = rest.split_once("<pre><code>") {
+        let (block, after_block) = block_start
+            .split_once("</code></pre>")
+            .unwrap_or((block_start, ""));
+        let text = after_block
+            .split("<pre><code>")
+            .next()
+            .unwrap_or(after_block);
+        examples.push(Example {
+            lines: html_to_text(block)
+                .trim_end_matches('\n')
+                .split('\n')
+                .map(String::from)
+                .collect(),
+            answer: emphasized_codes(text).last().map(html_to_text),
+        });
+        rest = after_block;
+    }
+    examples
+}
+
+/// Iterate over the content of `<code><em>...</em></code>` and `<em><code>...</code></em>`
+/// elements, the way answers are highlighted in puzzle descriptions
+fn emphasized_codes(html: &str) -> impl Iterator<Item = &str> {
+    ["<code><em>", "<em><code>"]
+        .iter()
+        .zip(["</em></code>", "</code></em>"].iter())
+        .flat_map(move |(start, end)| {
+            html.match_indices(start).filter_map(move |(index, _)| {
+                let content = &html[index + start.len()..];
+                content
+                    .find(end)
+                    .map(|content_end| (index, &content[..content_end]))
+            })
+        })
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .into_values()
+}
+
+/// Remove tags and decode entities
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+}
+
+pub fn decode_entities(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find('&') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, end))
+        });
+        match decoded {
+            Some((ch, end)) => {
+                result.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p>
+<pre><code>a &lt;= b
+<em>c</em> &amp;&amp; d
+</code></pre>
+<p>Here, <code>b</code> is used and the answer is <code><em>42</em></code>.</p>
+<p>Another example:</p>
+<pre><code>1,2
+</code></pre>
+<p>Nothing is emphasized here.</p>
+</article>
+<p>Your puzzle answer was <code>7</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>x -&gt; y</code></pre>
+<p>First <em><code>1</code></em>, finally <code><em>&#34;a&#x27;</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn decode_entities_test() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c &gt; d"),
+            "a < b && c > d"
+        );
+        assert_eq!(decode_entities("&#65;&#x42;&quot;"), "AB\"");
+        assert_eq!(decode_entities("a & b &unknown; &"), "a & b &unknown; &");
+    }
+
+    #[test]
+    fn html_to_text_test() {
+        assert_eq!(html_to_text("<em>a</em> &gt; <b>b</b>"), "a > b");
+    }
+
+    #[test]
+    fn parse_parts_test() {
+        assert_eq!(
+            parse_parts(PAGE),
+            vec![
+                vec![
+                    Example {
+                        lines: vec!["a <= b".into(), "c && d".into()],
+                        answer: Some("42".into()),
+                    },
+                    Example {
+                        lines: vec!["1,2".into()],
+                        answer: None,
+                    },
+                ],
+                vec![Example {
+                    lines: vec!["x -> y".into()],
+                    answer: Some("\"a'".into()),
+                }],
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_all_examples_test() {
+        let examples = parse_all_examples(PAGE);
+        assert_eq!(examples.len(), 3);
+        assert_eq!(examples[2].lines, vec!["x -> y"]);
+    }
+}
@@ -0,0 +1,295 @@
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Default baseline file name, see `baseline_path`
+pub const BASELINE_FILE: &str = "bench_baseline.json";
+
+/// Default slowdown tolerated before a run is flagged as a regression
+pub const DEFAULT_MARGIN: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub runs: usize,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "No sample to compute stats from");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        let seconds: Vec<_> = sorted.iter().map(Duration::as_secs_f64).collect();
+
+        let mean = seconds.iter().sum::<f64>() / runs as f64;
+        let variance = seconds
+            .iter()
+            .map(|seconds| (seconds - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        Self {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            runs,
+        }
+    }
+
+    /// Call `f` `runs` times after a warm-up call
+    pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> Self {
+        Self::measure_batched(runs, || (), |()| f())
+    }
+
+    /// Call `routine` `runs` times after a warm-up call, each time with a new value built by
+    /// `setup`. Only `routine` is timed.
+    pub fn measure_batched<I, T>(
+        runs: usize,
+        mut setup: impl FnMut() -> I,
+        mut routine: impl FnMut(I) -> T,
+    ) -> Self {
+        std::hint::black_box(routine(setup()));
+        let samples: Vec<_> = (0..runs)
+            .map(|_| {
+                let input = setup();
+                let start = Instant::now();
+                std::hint::black_box(routine(input));
+                start.elapsed()
+            })
+            .collect();
+        Self::from_samples(&samples)
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "mean_ns": self.mean.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "stddev_ns": self.stddev.as_nanos() as u64,
+            "runs": self.runs,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let nanos = |key: &str| Some(Duration::from_nanos(value.get(key)?.as_u64()?));
+        Some(Self {
+            mean: nanos("mean_ns")?,
+            median: nanos("median_ns")?,
+            stddev: nanos("stddev_ns")?,
+            runs: value.get("runs")?.as_u64()? as usize,
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mean {:?}, median {:?}, stddev {:?} ({} runs)",
+            self.mean, self.median, self.stddev, self.runs
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    /// Nothing to compare with
+    New,
+    /// Ratio of the current mean over the baseline mean
+    Ok(f64),
+    Regression(f64),
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::New => write!(f, "no baseline"),
+            Self::Ok(ratio) => write!(f, "{:+.1}%", (ratio - 1.) * 100.),
+            Self::Regression(ratio) => write!(f, "{:+.1}% REGRESSION", (ratio - 1.) * 100.),
+        }
+    }
+}
+
+/// Stats of previous runs, stored in a JSON file as an object indexed by benchmark name
+#[derive(Debug)]
+pub struct Baseline {
+    path: PathBuf,
+    entries: BTreeMap<String, Stats>,
+}
+
+impl Baseline {
+    /// Load a baseline file, empty if it doesn't exist yet
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            content => {
+                let value: Value = serde_json::from_str(&content?)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                value
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(name, stats)| Some((name.clone(), Stats::from_json(stats)?)))
+                    .collect()
+            }
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Stats> {
+        self.entries.get(name)
+    }
+
+    pub fn insert(&mut self, name: &str, stats: Stats) {
+        self.entries.insert(name.to_string(), stats);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let object: Map<_, _> = self
+            .entries
+            .iter()
+            .map(|(name, stats)| (name.clone(), stats.to_json()))
+            .collect();
+        let content = serde_json::to_string_pretty(&Value::Object(object))
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.path, content + "\n")
+    }
+
+    /// Compare the mean with the baseline one. A run is a regression if it is slower than the
+    /// baseline by more than `margin` (0.1 for 10%).
+    pub fn compare(&self, name: &str, stats: &Stats, margin: f64) -> Comparison {
+        match self.get(name) {
+            None => Comparison::New,
+            Some(baseline) => {
+                let ratio = stats.mean.as_secs_f64() / baseline.mean.as_secs_f64();
+                if ratio > 1. + margin {
+                    Comparison::Regression(ratio)
+                } else {
+                    Comparison::Ok(ratio)
+                }
+            }
+        }
+    }
+}
+
+/// Path of the baseline file: in the directory of the crate run by cargo, or in the working
+/// directory when not run by cargo
+pub fn baseline_path() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map_or_else(PathBuf::new, PathBuf::from)
+        .join(BASELINE_FILE)
+}
+
+/// Compare with the baseline of the crate, for benchmarks written as ignored tests.
+/// The margin is read from `AOC_BENCH_MARGIN` (in percent), and the baseline is updated when
+/// `AOC_BENCH_SAVE` is set.
+pub fn check(name: &str, stats: Stats) -> io::Result<Comparison> {
+    let margin = std::env::var("AOC_BENCH_MARGIN")
+        .ok()
+        .and_then(|margin| margin.parse::<f64>().ok())
+        .map_or(DEFAULT_MARGIN, |margin| margin / 100.);
+    let mut baseline = Baseline::load(baseline_path())?;
+    let comparison = baseline.compare(name, &stats, margin);
+    println!("{}: {} {}", name, stats, comparison);
+    if std::env::var_os("AOC_BENCH_SAVE").is_some() {
+        baseline.insert(name, stats);
+        baseline.save()?;
+    }
+    Ok(comparison)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 9, 5]));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_micros(4500));
+        assert_eq!(stats.stddev.as_micros(), 2549);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(
+            Stats::from_samples(&millis(&[3, 1, 2])).median,
+            Duration::from_millis(2)
+        );
+
+        let mut calls = 0;
+        assert_eq!(Stats::measure(5, || calls += 1).runs, 5);
+        assert_eq!(calls, 6);
+
+        let mut setups = 0;
+        let stats = Stats::measure_batched(
+            3,
+            || {
+                setups += 1;
+                std::thread::sleep(Duration::from_millis(20));
+            },
+            |()| {},
+        );
+        assert_eq!(setups, 4);
+        assert!(stats.median < Duration::from_millis(20));
+    }
+
+    #[test]
+    fn baseline_path_test() {
+        assert_eq!(
+            baseline_path(),
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(BASELINE_FILE)
+        );
+    }
+
+    #[test]
+    fn baseline_test() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut baseline = Baseline::load(&path).unwrap();
+        let stats = Stats::from_samples(&millis(&[10, 10]));
+        assert_eq!(baseline.compare("2025-01-1", &stats, 0.1), Comparison::New);
+        baseline.insert("2025-01-1", stats);
+        baseline.save().unwrap();
+
+        let baseline = Baseline::load(&path).unwrap();
+        assert_eq!(baseline.get("2025-01-1"), Some(&stats));
+        let faster = Stats::from_samples(&millis(&[9]));
+        let slower = Stats::from_samples(&millis(&[12]));
+        assert!(matches!(
+            baseline.compare("2025-01-1", &faster, 0.1),
+            Comparison::Ok(_)
+        ));
+        assert!(matches!(
+            baseline.compare("2025-01-1", &slower, 0.1),
+            Comparison::Regression(_)
+        ));
+        assert!(matches!(
+            baseline.compare("2025-01-1", &slower, 0.5),
+            Comparison::Ok(_)
+        ));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,43 @@
+use aoc::account::{self, Account};
+
+const USAGE: &str = "\
+Usage: accounts (add SESSION [NAME] | remove NAME | list)
+
+Accounts are registered in the file pointed by AOC_ACCOUNTS, or ~/.aoc_accounts. Select one with
+the AOC_PROFILE environment variable, or check every account with `run --accounts`.";
+
+fn main() {
+    if let Err(message) = run(std::env::args().skip(1).collect()) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let path = account::accounts_path();
+    let mut accounts = account::load_accounts(&path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["add", session] | ["add", session, _] => {
+            let mut account = Account::from_session(session);
+            if let Some(name) = args.get(2) {
+                account.name = name.clone();
+            }
+            let name = account.name.clone();
+            account::register(&path, account).map(|()| println!("Registered {}", name))
+        }
+        ["remove", name] => {
+            accounts.retain(|account| account.name != name);
+            account::save_accounts(&path, &accounts)
+        }
+        ["list"] => {
+            for account in accounts {
+                println!("{}", account.name);
+            }
+            Ok(())
+        }
+        _ => return Err(USAGE.to_string()),
+    }
+    .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+}
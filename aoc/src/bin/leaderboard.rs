@@ -0,0 +1,38 @@
+use aoc::leaderboard::Leaderboard;
+use aoc::util::Puzzles;
+
+const USAGE: &str = "\
+Usage: leaderboard YEAR ID [--details]
+
+Print the ranking of the private leaderboard ID. The leaderboard is cached, and fetched again at
+most once every 15 minutes.";
+
+fn main() {
+    if let Err(message) = run(std::env::args().skip(1).collect()) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let (year, id, details) = match &args[..] {
+        [year, id] => (year, id, false),
+        [year, id, flag] if flag == "--details" => (year, id, true),
+        _ => return Err(USAGE.to_string()),
+    };
+    let year = year.parse().map_err(|_| USAGE)?;
+    let id = id.parse().map_err(|_| USAGE)?;
+
+    let json = Puzzles::new(year)
+        .leaderboard(id)
+        .map_err(|error| format!("Failed to fetch the leaderboard: {}", error))?;
+    let leaderboard = Leaderboard::parse(&json)
+        .map_err(|error| format!("Failed to parse the leaderboard: {}", error))?;
+
+    print!("{}", leaderboard.table());
+    if details {
+        println!();
+        print!("{}", leaderboard.details());
+    }
+    Ok(())
+}
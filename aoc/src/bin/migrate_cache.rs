@@ -0,0 +1,26 @@
+use aoc::cache::Cache;
+
+/// Encrypt the plain text files of the given cache directories, including their account sub
+/// directories, with the key configured by `AOC_CACHE_KEY` or `AOC_CACHE_KEY_FILE`
+fn main() {
+    let dirs: Vec<String> = std::env::args().skip(1).collect();
+    if dirs.is_empty() {
+        eprintln!("Usage: migrate_cache CACHE_DIR...");
+        std::process::exit(1);
+    }
+
+    for dir in dirs {
+        let cache = Cache::from_env(&dir);
+        if !cache.is_encrypted() {
+            eprintln!("AOC_CACHE_KEY or AOC_CACHE_KEY_FILE is required to encrypt the cache");
+            std::process::exit(1);
+        }
+        match cache.migrate() {
+            Ok(files) => println!("{}: {} files encrypted", dir, files.len()),
+            Err(error) => {
+                eprintln!("{}: {}", dir, error);
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use aoc::scaffold;
+use aoc::util::Puzzles;
+use std::path::Path;
+use std::process::Command;
+
+const USAGE: &str = "\
+Usage: new YEAR [DAY]
+
+Run from the repository root. Create the YEAR crate if it doesn't exist, and the module of DAY
+with its input and examples fetched into the cache of the crate.";
+
+fn main() {
+    if let Err(message) = run(std::env::args().skip(1).collect()) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let (year, day): (u16, Option<u8>) = match &args[..] {
+        [year] => (year.parse().map_err(|_| USAGE)?, None),
+        [year, day] => (
+            year.parse().map_err(|_| USAGE)?,
+            Some(day.parse().map_err(|_| USAGE)?),
+        ),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let dir = Path::new(&year.to_string()).to_path_buf();
+    if !dir.exists() {
+        scaffold::create_year(&dir, year).map_err(|error| error.to_string())?;
+        println!("Created {}", dir.display());
+    }
+
+    if let Some(day) = day {
+        scaffold::create_day(&dir, day).map_err(|error| error.to_string())?;
+        println!("Created {}/src/day{:02}.rs", dir.display(), day);
+        // Formatting is not critical, ignore failures
+        let _ = Command::new("cargo").arg("fmt").current_dir(&dir).status();
+
+        // Same cache directory as the crate, including the account sub directory
+        let mut puzzles = Puzzles::new(year);
+        puzzles.cache = puzzles.cache.rebased(&dir);
+        match puzzles.prefetch(day) {
+            Ok(examples) => println!("Fetched the input and {} examples", examples),
+            Err(error) => eprintln!("Failed to fetch the input: {}", error),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,423 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Prefix of every encrypted cache file
+const MAGIC: &[u8] = b"aoc-encrypted-1\n";
+
+const NONCE_LEN: usize = 12;
+
+/// File marking a cache directory as encrypted. It contains an encrypted known value, so a wrong
+/// key is detected before anything is written with it.
+const MARKER: &str = ".encrypted";
+
+const MARKER_CONTENT: &str = "advent of code cache";
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The cache directory is encrypted, but no key is configured
+    MissingKey,
+    /// The configured key doesn't match the one used to encrypt the cache directory
+    WrongKey,
+    /// An encrypted file could not be decrypted
+    Corrupted(PathBuf),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::MissingKey => write!(
+                f,
+                "the cache is encrypted, set AOC_CACHE_KEY or AOC_CACHE_KEY_FILE to use it"
+            ),
+            Self::WrongKey => write!(f, "the cache key doesn't match the encrypted cache"),
+            Self::Corrupted(path) => write!(f, "failed to decrypt {}", path.display()),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Directory storing fetched puzzle data. When a key is configured, files are encrypted with
+/// ChaCha20-Poly1305 so the directory can be committed without redistributing the inputs.
+#[derive(Clone)]
+pub struct Cache {
+    pub dir: PathBuf,
+    /// Top directory of the cache, holding the encryption marker of `dir` and its sub directories
+    root: PathBuf,
+    key: Option<Key>,
+}
+
+impl fmt::Debug for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("dir", &self.dir)
+            .field("encrypted", &self.key.is_some())
+            .finish()
+    }
+}
+
+impl Cache {
+    /// Plain text cache, usable as long as the directory is not encrypted
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        Self {
+            root: dir.clone(),
+            dir,
+            key: None,
+        }
+    }
+
+    /// Cache encrypted with a key derived from the given secret
+    pub fn with_secret(dir: impl Into<PathBuf>, secret: &str) -> Self {
+        let dir = dir.into();
+        Self {
+            root: dir.clone(),
+            dir,
+            key: Some(derive_key(secret)),
+        }
+    }
+
+    /// Read the secret from the `AOC_CACHE_KEY` environment variable, or from the file pointed by
+    /// `AOC_CACHE_KEY_FILE`
+    pub fn from_env(dir: impl Into<PathBuf>) -> Self {
+        let secret = std::env::var("AOC_CACHE_KEY").ok().or_else(|| {
+            let path = std::env::var("AOC_CACHE_KEY_FILE").ok()?;
+            let secret = fs::read_to_string(&path)
+                .unwrap_or_else(|error| panic!("Failed to read the key file {}: {}", path, error));
+            Some(secret.trim().to_string())
+        });
+        match secret {
+            Some(secret) => Self::with_secret(dir, &secret),
+            None => Self::new(dir),
+        }
+    }
+
+    /// Cache stored in a sub directory, sharing the key and the encryption state of this one.
+    /// Files missing from the sub directory are read from the root, so entries cached before the
+    /// sub directory existed are still used.
+    pub fn subdir(&self, name: impl AsRef<Path>) -> Self {
+        Self {
+            dir: self.dir.join(name),
+            root: self.root.clone(),
+            key: self.key,
+        }
+    }
+
+    /// Same cache, with relative directories resolved from `base` instead of the working
+    /// directory
+    pub fn rebased(&self, base: &Path) -> Self {
+        Self {
+            dir: base.join(&self.dir),
+            root: base.join(&self.root),
+            key: self.key,
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Time since a cache file was written, or `None` if it doesn't exist
+    pub fn age(&self, name: &str) -> Result<Option<Duration>, Error> {
+        let metadata = match fs::metadata(self.dir.join(name)) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            metadata => metadata?,
+        };
+        Ok(Some(metadata.modified()?.elapsed().unwrap_or_default()))
+    }
+
+    /// Content of a cache file, or `None` if it doesn't exist. Plain text files are still readable
+    /// when a key is configured, so a cache can be used while it is being migrated.
+    pub fn read(&self, name: &str) -> Result<Option<String>, Error> {
+        match self.read_file(self.dir.join(name), name)? {
+            None if self.dir != self.root => self.read_file(self.root.join(name), name),
+            content => Ok(content),
+        }
+    }
+
+    fn read_file(&self, path: PathBuf, name: &str) -> Result<Option<String>, Error> {
+        let data = match fs::read(&path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            data => data?,
+        };
+
+        let content = match data.strip_prefix(MAGIC) {
+            Some(data) => {
+                let key = self.key.as_ref().ok_or(Error::MissingKey)?;
+                decrypt(key, name, data).ok_or(Error::Corrupted(path))?
+            }
+            None => data,
+        };
+
+        String::from_utf8(content)
+            .map(Some)
+            .map_err(|error| Error::Io(io::Error::new(io::ErrorKind::InvalidData, error)))
+    }
+
+    /// Write a cache file, encrypted if a key is configured. Writing to an encrypted cache
+    /// directory without the right key fails instead of leaking plain text.
+    pub fn write(&self, name: &str, content: &str) -> Result<(), Error> {
+        self.check_key()?;
+        fs::create_dir_all(&self.dir)?;
+        let data = match &self.key {
+            Some(key) => {
+                self.write_marker(key)?;
+                encrypt(key, name, content.as_bytes())
+            }
+            None => content.as_bytes().to_vec(),
+        };
+        fs::write(self.dir.join(name), data)?;
+        Ok(())
+    }
+
+    /// Encrypt every plain text file of the directory and its sub directories, and mark the
+    /// cache as encrypted. Returns the paths of the converted files, relative to the directory.
+    pub fn migrate(&self) -> Result<Vec<String>, Error> {
+        let key = self.key.as_ref().ok_or(Error::MissingKey)?;
+        self.check_key()?;
+        fs::create_dir_all(&self.dir)?;
+
+        let mut migrated = Vec::new();
+        migrate_dir(key, &self.dir, "", &mut migrated)?;
+        migrated.sort();
+
+        self.write_marker(key)?;
+        Ok(migrated)
+    }
+
+    /// Mark the directory as encrypted with `key`, if it isn't already
+    fn write_marker(&self, key: &Key) -> Result<(), Error> {
+        if !self.marker_path().exists() {
+            fs::write(
+                self.marker_path(),
+                encrypt(key, MARKER, MARKER_CONTENT.as_bytes()),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn marker_path(&self) -> PathBuf {
+        self.root.join(MARKER)
+    }
+
+    /// Make sure the configured key, if any, is the one of an encrypted cache
+    fn check_key(&self) -> Result<(), Error> {
+        let marker = match fs::read(self.marker_path()) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            marker => marker?,
+        };
+        let key = self.key.as_ref().ok_or(Error::MissingKey)?;
+        match marker
+            .strip_prefix(MAGIC)
+            .and_then(|data| decrypt(key, MARKER, data))
+        {
+            Some(content) if content == MARKER_CONTENT.as_bytes() => Ok(()),
+            _ => Err(Error::WrongKey),
+        }
+    }
+}
+
+/// Encrypt the plain text files of `dir`, recursively. Files are encrypted with their own name
+/// only, the way `Cache::write` does in the sub directory holding them.
+fn migrate_dir(
+    key: &Key,
+    dir: &Path,
+    prefix: &str,
+    migrated: &mut Vec<String>,
+) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            migrate_dir(
+                key,
+                &entry.path(),
+                &format!("{}{}/", prefix, name),
+                migrated,
+            )?;
+        } else if file_type.is_file() {
+            let data = fs::read(entry.path())?;
+            if !data.starts_with(MAGIC) {
+                fs::write(entry.path(), encrypt(key, &name, &data))?;
+                migrated.push(format!("{}{}", prefix, name));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn derive_key(secret: &str) -> Key {
+    Sha256::digest(secret.as_bytes())
+}
+
+/// The file name is authenticated too, so encrypted files can't be swapped
+fn encrypt(key: &Key, name: &str, content: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: content,
+        aad: name.as_bytes(),
+    };
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, payload)
+        .expect("Failed to encrypt");
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+fn decrypt(key: &Key, name: &str, data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: name.as_bytes(),
+    };
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), payload)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn encrypted_round_trip_test() {
+        let cache = Cache::with_secret(temp_dir("round-trip"), "secret");
+
+        cache.write("input_01", "1\n2\n3").unwrap();
+        let data = fs::read(cache.dir.join("input_01")).unwrap();
+        assert!(data.starts_with(MAGIC));
+        assert!(!data.windows(3).any(|window| window == b"1\n2"));
+        assert_eq!(cache.read("input_01").unwrap().unwrap(), "1\n2\n3");
+        assert!(cache.read("input_02").unwrap().is_none());
+
+        // Encrypted files can't be read without a key, or renamed
+        let plain = Cache::new(&cache.dir);
+        assert!(matches!(plain.read("input_01"), Err(Error::MissingKey)));
+        fs::rename(cache.dir.join("input_01"), cache.dir.join("input_02")).unwrap();
+        assert!(matches!(cache.read("input_02"), Err(Error::Corrupted(_))));
+
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn encrypted_write_marks_directory_test() {
+        let dir = temp_dir("marker");
+        Cache::with_secret(&dir, "secret")
+            .write("input_01", "abc")
+            .unwrap();
+
+        assert!(matches!(
+            Cache::new(&dir).write("input_02", "def"),
+            Err(Error::MissingKey)
+        ));
+        assert!(matches!(
+            Cache::with_secret(&dir, "other").write("input_02", "def"),
+            Err(Error::WrongKey)
+        ));
+        assert!(!dir.join("input_02").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn migrate_test() {
+        let dir = temp_dir("migrate");
+        let plain = Cache::new(&dir);
+        plain.write("input_01", "abc").unwrap();
+        plain.write("answer_01_1", "42").unwrap();
+
+        let cache = Cache::with_secret(&dir, "secret");
+        // Not migrated files are still readable
+        assert_eq!(cache.read("input_01").unwrap().unwrap(), "abc");
+        assert_eq!(cache.migrate().unwrap(), ["answer_01_1", "input_01"]);
+        assert!(cache.migrate().unwrap().is_empty());
+        assert_eq!(cache.read("answer_01_1").unwrap().unwrap(), "42");
+        assert!(fs::read(dir.join("input_01")).unwrap().starts_with(MAGIC));
+
+        // Once migrated, the directory refuses plain text and other keys
+        assert!(matches!(
+            plain.write("input_02", "def"),
+            Err(Error::MissingKey)
+        ));
+        let other = Cache::with_secret(&dir, "other");
+        assert!(matches!(
+            other.write("input_02", "def"),
+            Err(Error::WrongKey)
+        ));
+        assert!(!dir.join("input_02").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn migrate_subdir_test() {
+        let dir = temp_dir("migrate-subdir");
+        let plain = Cache::new(&dir);
+        plain.write("input_01", "abc").unwrap();
+        plain.subdir("account").write("input_01", "def").unwrap();
+
+        let cache = Cache::with_secret(&dir, "secret");
+        assert_eq!(cache.migrate().unwrap(), ["account/input_01", "input_01"]);
+        assert!(fs::read(dir.join("account/input_01"))
+            .unwrap()
+            .starts_with(MAGIC));
+        assert_eq!(
+            cache.subdir("account").read("input_01").unwrap().unwrap(),
+            "def"
+        );
+
+        // The marker of the root applies to the sub directories
+        assert!(!dir.join("account").join(MARKER).exists());
+        assert!(matches!(
+            plain.subdir("account").write("input_02", "ghi"),
+            Err(Error::MissingKey)
+        ));
+        assert!(matches!(
+            plain.subdir("other").write("input_01", "ghi"),
+            Err(Error::MissingKey)
+        ));
+        assert!(!dir.join("other").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn subdir_fallback_test() {
+        let dir = temp_dir("fallback");
+        let cache = Cache::new(&dir);
+        cache.write("input_01", "flat").unwrap();
+        let account = cache.subdir("account");
+
+        assert_eq!(account.read("input_01").unwrap().unwrap(), "flat");
+        account.write("input_01", "account").unwrap();
+        assert_eq!(account.read("input_01").unwrap().unwrap(), "account");
+        assert_eq!(cache.read("input_01").unwrap().unwrap(), "flat");
+        assert!(account.read("input_02").unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
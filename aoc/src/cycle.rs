@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Describe a repetition in a sequence of states: the state at step `start + length` is the same
+/// as the state at step `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Return the smallest step producing the same state as the step `n`
+    pub fn equivalent_step(self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Find a cycle by remembering every state. Prefer this when states are cheap to hash.
+pub fn find_cycle<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    find_cycle_by_key(initial, step, Clone::clone)
+}
+
+/// Find a cycle by comparing fingerprints of states instead of the states themselves. Two states
+/// with the same fingerprint are considered equal.
+pub fn find_cycle_by_key<S, K, F, G>(initial: S, mut step: F, mut key: G) -> Cycle
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for index in 0.. {
+        if let Some(start) = seen.insert(key(&state), index) {
+            return Cycle {
+                start,
+                length: index - start,
+            };
+        }
+        state = step(&state);
+    }
+    unreachable!()
+}
+
+/// Find a cycle using Brent's algorithm. It does not need to hash states and only keeps two of
+/// them in memory, but runs the step function more often.
+pub fn find_cycle_brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Return the state after `n` steps, skipping the repetitions once a cycle is found
+pub fn nth_state<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    nth_state_by_key(initial, step, Clone::clone, n)
+}
+
+/// Same as `nth_state`, but states are compared through their fingerprint. The returned state is
+/// the first one seen with the fingerprint of the requested step.
+pub fn nth_state_by_key<S, K, F, G>(initial: S, mut step: F, mut key: G, n: usize) -> S
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![initial];
+    loop {
+        let index = history.len() - 1;
+        if index == n {
+            return history.pop().unwrap();
+        }
+        if let Some(start) = seen.insert(key(&history[index]), index) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(n));
+        }
+        let next = step(&history[index]);
+        history.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2 -> ...
+    fn step(n: &u32) -> u32 {
+        if *n == 5 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn find_cycle_test() {
+        assert_eq!(
+            find_cycle(0, step),
+            Cycle {
+                start: 2,
+                length: 4
+            }
+        );
+        assert_eq!(
+            find_cycle(3, step),
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+    }
+
+    #[test]
+    fn find_cycle_brent_test() {
+        assert_eq!(
+            find_cycle_brent(0, step),
+            Cycle {
+                start: 2,
+                length: 4
+            }
+        );
+        assert_eq!(
+            find_cycle_brent(3, step),
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+    }
+
+    #[test]
+    fn find_cycle_by_key_test() {
+        // Shifting pattern, like 2018 day 12 plants: only the shape repeats
+        let step = |(offset, pattern): &(i64, u8)| {
+            if *pattern == 0b11 {
+                (offset + 1, 0b11)
+            } else {
+                (*offset, pattern << 1 | 1)
+            }
+        };
+        assert_eq!(
+            find_cycle_by_key((0, 0), step, |(_, pattern)| *pattern),
+            Cycle {
+                start: 2,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn nth_state_test() {
+        assert_eq!(nth_state(0, step, 0), 0);
+        assert_eq!(nth_state(0, step, 5), 5);
+        assert_eq!(nth_state(0, step, 6), 2);
+        assert_eq!(
+            nth_state(0, step, 1_000_000_000),
+            2 + (1_000_000_000 - 2) % 4
+        );
+    }
+
+    #[test]
+    fn equivalent_step_test() {
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(8), 3);
+        assert_eq!(cycle.equivalent_step(14), 4);
+    }
+}
@@ -0,0 +1,461 @@
+use native_tls::TlsConnector;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/BenoitZugmeyer/RustyAdventOfCode by bzugmeyer@gmail.com";
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    Io(io::Error),
+    Tls(String),
+    InvalidResponse(String),
+    Status(u16, String),
+    /// The server answered with a page asking to log in: the session is missing or expired
+    LoggedOut,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingSession => write!(f, "AOC_SESSION environment variable is missing"),
+            Self::Io(error) => write!(f, "Connection error: {}", error),
+            Self::Tls(error) => write!(f, "TLS error: {}", error),
+            Self::InvalidResponse(reason) => write!(f, "Invalid response: {}", reason),
+            Self::Status(code, reason) => write!(f, "Got a {} {}", code, reason),
+            Self::LoggedOut => write!(f, "Not logged in, the AOC_SESSION may have expired"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl Error {
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Io(_) => true,
+            Self::Status(code, _) => *code >= 500 || *code == 429,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Client for the Advent of Code website. The server is `https://adventofcode.com` unless
+/// overridden by the `AOC_URL` environment variable (ex: `http://127.0.0.1:8080` to use a local
+/// server).
+#[derive(Debug, Clone)]
+pub struct Client {
+    host: String,
+    port: u16,
+    tls: bool,
+    session: Option<String>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl Client {
+    pub fn from_env() -> Self {
+        let url = std::env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.into());
+        Self::new(&url, std::env::var("AOC_SESSION").ok())
+    }
+
+    pub fn new(url: &str, session: Option<String>) -> Self {
+        let (tls, address) = if let Some(address) = url.strip_prefix("http://") {
+            (false, address)
+        } else {
+            (true, url.strip_prefix("https://").unwrap_or(url))
+        };
+        let address = address.trim_end_matches('/');
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().expect("Invalid port in AOC_URL")),
+            None => (address, if tls { 443 } else { 80 }),
+        };
+        Self {
+            host: host.to_string(),
+            port,
+            tls,
+            session,
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Number of times a GET request is retried after a connection error or a server error, the
+    /// delay doubling after each attempt. Other requests are never retried, as the server may have
+    /// processed them already.
+    pub fn with_retries(mut self, retries: u32, retry_delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        self.session = Some(session.to_string());
+        self
+    }
+
+    pub fn get(&self, path: &str) -> Result<Response, Error> {
+        self.send("GET", path, None, self.retries)
+    }
+
+    pub fn post_form(&self, path: &str, body: &str) -> Result<Response, Error> {
+        self.send("POST", path, Some(body), 0)
+    }
+
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+        retries: u32,
+    ) -> Result<Response, Error> {
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            match self.send_once(method, path, body) {
+                Err(error) if error.is_transient() && attempt < retries => {
+                    attempt += 1;
+                    thread::sleep(delay);
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn send_once(&self, method: &str, path: &str, body: Option<&str>) -> Result<Response, Error> {
+        let session = self.session.as_ref().ok_or(Error::MissingSession)?;
+        let stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        let mut stream: Box<dyn ReadWrite> = if self.tls {
+            let connector = TlsConnector::new().map_err(|error| Error::Tls(error.to_string()))?;
+            Box::new(
+                connector
+                    .connect(&self.host, stream)
+                    .map_err(|error| Error::Tls(error.to_string()))?,
+            )
+        } else {
+            Box::new(stream)
+        };
+
+        let body_headers = body.map_or(String::new(), |body| {
+            format!(
+                "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n",
+                body.len()
+            )
+        });
+        write!(
+            stream,
+            "\
+             {} {} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             User-Agent: {}\r\n\
+             Cookie: session={}\r\n\
+             Connection: close\r\n\
+             {}\
+             \r\n\
+             {}",
+            method,
+            path,
+            self.host,
+            USER_AGENT,
+            session,
+            body_headers,
+            body.unwrap_or("")
+        )?;
+        stream.flush()?;
+
+        let response = read_response(BufReader::new(stream))?;
+        if is_logged_out(&response) {
+            Err(Error::LoggedOut)
+        } else if !(200..300).contains(&response.status) {
+            Err(Error::Status(response.status, response.reason))
+        } else {
+            Ok(response)
+        }
+    }
+}
+
+trait ReadWrite: Read + Write {}
+impl<T: Read + Write> ReadWrite for T {}
+
+fn is_logged_out(response: &Response) -> bool {
+    response.body.contains("Please log in")
+        || response.body.contains("/auth/login\">[Log In]")
+        || response.status == 302
+            && response
+                .header("Location")
+                .is_some_and(|location| location.contains("/auth/login"))
+}
+
+fn read_response(mut reader: impl BufRead) -> Result<Response, Error> {
+    let invalid = |reason: &str| Error::InvalidResponse(reason.to_string());
+
+    let status_line = read_line(&mut reader)?;
+    let mut parts = status_line.splitn(3, ' ');
+    let version = parts.next().unwrap_or_default();
+    if !version.starts_with("HTTP/") {
+        return Err(invalid(&format!("bad status line {:?}", status_line)));
+    }
+    let status = parts
+        .next()
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| invalid(&format!("bad status code in {:?}", status_line)))?;
+    let reason = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(&mut reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(&format!("bad header {:?}", line)))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut response = Response {
+        status,
+        reason,
+        headers,
+        body: String::new(),
+    };
+
+    let body = if response
+        .header("Transfer-Encoding")
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"))
+    {
+        read_chunked_body(&mut reader)?
+    } else if let Some(length) = response.header("Content-Length") {
+        let length = length
+            .parse()
+            .map_err(|_| invalid(&format!("bad Content-Length {:?}", length)))?;
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        body
+    } else {
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body)?;
+        body
+    };
+
+    response.body = String::from_utf8(body).map_err(|_| invalid("body is not UTF-8"))?;
+    Ok(response)
+}
+
+fn read_chunked_body(reader: &mut impl BufRead) -> Result<Vec<u8>, Error> {
+    let mut body = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| Error::InvalidResponse(format!("bad chunk size {:?}", line)))?;
+        if size == 0 {
+            // Skip trailers
+            while !read_line(reader)?.is_empty() {}
+            return Ok(body);
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        read_line(reader)?;
+    }
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String, Error> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(Error::InvalidResponse("unexpected end of response".into()));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::Client;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Start a server answering the given raw responses, one per connection. Return a client
+    /// using it, and a handle to get the received requests.
+    pub fn server(responses: Vec<String>) -> (Client, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let request = read_request(&mut BufReader::new(&mut stream));
+                    stream.write_all(response.as_bytes()).unwrap();
+                    request
+                })
+                .collect()
+        });
+        let client =
+            Client::new(&url, Some("secret".into())).with_retries(2, Duration::from_millis(1));
+        (client, handle)
+    }
+
+    pub fn response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    pub fn ok(body: &str) -> String {
+        response("200 OK", body)
+    }
+
+    fn read_request(reader: &mut impl BufRead) -> String {
+        let mut request = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                length = value.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        request + &String::from_utf8(body).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_response_test() {
+        let response = read_response(
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nContent-Length: 4\r\n\r\nabcdef"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.reason, "Not Found");
+        assert_eq!(response.header("content-type"), Some("text/plain"));
+        assert_eq!(response.body, "abcd");
+
+        let response = read_response(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nabcd\r\n3;x=y\r\nefg\r\n0\r\n\r\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(response.body, "abcdefg");
+
+        let response = read_response("HTTP/1.0 200 OK\r\n\r\nuntil\nthe end".as_bytes()).unwrap();
+        assert_eq!(response.body, "until\nthe end");
+
+        assert!(read_response("garbage\r\n\r\n".as_bytes()).is_err());
+        assert!(
+            read_response("HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nabc".as_bytes()).is_err()
+        );
+    }
+
+    #[test]
+    fn client_new_test() {
+        let client = Client::new("https://adventofcode.com", None);
+        assert_eq!(
+            (client.host.as_str(), client.port, client.tls),
+            ("adventofcode.com", 443, true)
+        );
+        let client = Client::new("http://127.0.0.1:8080/", None);
+        assert_eq!(
+            (client.host.as_str(), client.port, client.tls),
+            ("127.0.0.1", 8080, false)
+        );
+    }
+
+    #[test]
+    fn get_test() {
+        let (client, server) = mock::server(vec![mock::ok("hello")]);
+        assert_eq!(client.get("/2025/day/1").unwrap().body, "hello");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/1 HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=secret\r\n"));
+        assert!(requests[0].contains(&format!("\r\nUser-Agent: {}\r\n", USER_AGENT)));
+    }
+
+    #[test]
+    fn retry_test() {
+        let (client, server) = mock::server(vec![
+            mock::response("503 Service Unavailable", ""),
+            mock::ok("finally"),
+        ]);
+        assert_eq!(client.get("/").unwrap().body, "finally");
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        let (client, server) = mock::server(vec![mock::response("404 Not Found", "")]);
+        assert!(matches!(client.get("/"), Err(Error::Status(404, _))));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn post_not_retried_test() {
+        let (client, server) = mock::server(vec![
+            mock::response("502 Bad Gateway", ""),
+            mock::ok("next request"),
+        ]);
+        assert!(matches!(
+            client.post_form("/2025/day/1/answer", "level=1&answer=42"),
+            Err(Error::Status(502, _))
+        ));
+        assert_eq!(client.get("/").unwrap().body, "next request");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[1].starts_with("GET / HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn logged_out_test() {
+        let (client, server) = mock::server(vec![mock::response(
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+        assert!(matches!(
+            client.get("/2025/day/1/input"),
+            Err(Error::LoggedOut)
+        ));
+        server.join().unwrap();
+
+        let client = Client::new("http://127.0.0.1:1", None);
+        assert!(matches!(client.get("/"), Err(Error::MissingSession)));
+    }
+}
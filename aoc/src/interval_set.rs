@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::iter::{FromIterator, Sum};
+use std::ops::{Range, Sub};
+
+/// A set of values stored as sorted, disjoint and non-adjacent half-open intervals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    // Interval start => interval end
+    intervals: BTreeMap<T, T>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a range to the set, merging it with overlapping or adjacent intervals
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let Range { mut start, mut end } = range;
+
+        if let Some((&previous_start, &previous_end)) = self.intervals.range(..=start).next_back() {
+            if previous_end >= start {
+                start = previous_start;
+                end = end.max(previous_end);
+            }
+        }
+
+        let merged: Vec<T> = self.intervals.range(start..=end).map(|(s, _)| *s).collect();
+        for merged_start in merged {
+            end = end.max(self.intervals.remove(&merged_start).unwrap());
+        }
+
+        self.intervals.insert(start, end);
+    }
+
+    /// Remove a range from the set, splitting intervals partially covered by it
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&previous_start, &previous_end)) =
+            self.intervals.range(..range.start).next_back()
+        {
+            if previous_end > range.start {
+                self.intervals.insert(previous_start, range.start);
+                if previous_end > range.end {
+                    self.intervals.insert(range.end, previous_end);
+                }
+            }
+        }
+
+        let removed: Vec<(T, T)> = self
+            .intervals
+            .range(range.clone())
+            .map(|(s, e)| (*s, *e))
+            .collect();
+        for (removed_start, removed_end) in removed {
+            self.intervals.remove(&removed_start);
+            if removed_end > range.end {
+                self.intervals.insert(range.end, removed_end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.intervals
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, end)| end > value)
+    }
+
+    /// Number of disjoint intervals
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().map(|(start, end)| *start..*end)
+    }
+
+    /// Iterate over the ranges within `bounds` that are not in the set
+    pub fn gaps(&self, bounds: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let mut position = bounds.start;
+        self.iter()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .filter_map(move |interval| {
+                let gap_end = interval
+                    .as_ref()
+                    .map_or(bounds.end, |interval| interval.start.min(bounds.end));
+                let gap = position..gap_end;
+                if let Some(interval) = interval {
+                    position = position.max(interval.end);
+                }
+                if gap.is_empty() {
+                    None
+                } else {
+                    Some(gap)
+                }
+            })
+    }
+
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        self.gaps(bounds).collect()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.difference(&self.difference(other))
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// Total number of values covered by the set
+    pub fn covered_length(&self) -> T {
+        self.iter().map(|range| range.end - range.start).sum()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(u32, u32)]) -> IntervalSet<u32> {
+        ranges.iter().map(|&(start, end)| start..end).collect()
+    }
+
+    #[test]
+    fn insert_test() {
+        assert_eq!(
+            set(&[(3, 5), (10, 14), (16, 20), (12, 18)]),
+            set(&[(3, 5), (10, 20)])
+        );
+        // Adjacent ranges are merged
+        assert_eq!(set(&[(3, 5), (5, 8)]).len(), 1);
+        assert_eq!(set(&[(3, 5), (6, 8)]).len(), 2);
+        assert_eq!(set(&[(3, 5), (0, 10)]), set(&[(0, 10)]));
+        assert_eq!(set(&[(3, 5), (4, 4)]), set(&[(3, 5)]));
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut s = set(&[(0, 10), (20, 30)]);
+        s.remove(5..22);
+        assert_eq!(s, set(&[(0, 5), (22, 30)]));
+        s.remove(24..26);
+        assert_eq!(s, set(&[(0, 5), (22, 24), (26, 30)]));
+        s.remove(0..100);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn contains_test() {
+        let s = set(&[(3, 5), (10, 14)]);
+        assert!(!s.contains(&2));
+        assert!(s.contains(&3));
+        assert!(s.contains(&4));
+        assert!(!s.contains(&5));
+        assert!(s.contains(&13));
+        assert!(!s.contains(&14));
+    }
+
+    #[test]
+    fn gaps_test() {
+        let s = set(&[(3, 5), (10, 14)]);
+        assert_eq!(s.gaps(0..20).collect::<Vec<_>>(), vec![0..3, 5..10, 14..20]);
+        assert_eq!(s.gaps(4..12).collect::<Vec<_>>(), vec![5..10]);
+        assert_eq!(s.gaps(3..5).count(), 0);
+        assert_eq!(s.complement(0..20), set(&[(0, 3), (5, 10), (14, 20)]));
+    }
+
+    #[test]
+    fn set_operations_test() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+    }
+
+    #[test]
+    fn covered_length_test() {
+        assert_eq!(set(&[(3, 5), (10, 14), (12, 18)]).covered_length(), 10);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::http;
+use crate::util::Puzzles;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// adventofcode.com asks not to fetch a private leaderboard more than once every 15 minutes
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub stars: u32,
+    /// Timestamps of the two stars of each day
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub num_days: u8,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let field = |value: &Value, name: &str| -> Result<Value, String> {
+            value
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Missing field {}", name))
+        };
+
+        let year = match field(&value, "event")? {
+            Value::String(event) => event.parse().ok(),
+            event => event.as_u64().and_then(|event| event.try_into().ok()),
+        }
+        .ok_or("Invalid event")?;
+        let num_days = value
+            .get("num_days")
+            .and_then(Value::as_u64)
+            .map_or(25, |num_days| num_days as u8);
+
+        let mut members = Vec::new();
+        for member in field(&value, "members")?
+            .as_object()
+            .ok_or("Invalid members")?
+            .values()
+        {
+            let id = field(member, "id")?.as_u64().ok_or("Invalid member id")?;
+            let name = match member.get("name").and_then(Value::as_str) {
+                Some(name) => name.to_string(),
+                None => format!("(anonymous user #{})", id),
+            };
+            let mut days = BTreeMap::new();
+            for (day, parts) in member
+                .get("completion_day_level")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                let day = day.parse().map_err(|_| format!("Invalid day {}", day))?;
+                let star = |part: &str| parts.get(part)?.get("get_star_ts")?.as_u64();
+                days.insert(day, [star("1"), star("2")]);
+            }
+            members.push(Member {
+                id,
+                name,
+                stars: member.get("stars").and_then(Value::as_u64).unwrap_or(0) as u32,
+                days,
+            });
+        }
+        members.sort_by_key(|member| member.id);
+
+        Ok(Self {
+            year,
+            num_days,
+            members,
+        })
+    }
+
+    /// Local score of each member, in the order of `members`: for each star, the first member to
+    /// get it gets as many points as there are members, the second one point less, and so on
+    pub fn local_scores(&self) -> Vec<u32> {
+        let mut scores = vec![0; self.members.len()];
+        for day in 1..=self.num_days {
+            for part in 0..2 {
+                let mut finishers: Vec<_> = self
+                    .members
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, member)| Some((member.days.get(&day)?[part]?, index)))
+                    .collect();
+                finishers.sort();
+                for (rank, (_, index)) in finishers.into_iter().enumerate() {
+                    scores[index] += (self.members.len() - rank) as u32;
+                }
+            }
+        }
+        scores
+    }
+
+    /// Members sorted by decreasing local score, with their score
+    pub fn ranking(&self) -> Vec<(u32, &Member)> {
+        let mut ranking: Vec<_> = self.local_scores().into_iter().zip(&self.members).collect();
+        ranking.sort_by_key(|(score, member)| (std::cmp::Reverse(*score), member.id));
+        ranking
+    }
+
+    /// Ranking table, with the stars of each day like on the website
+    pub fn table(&self) -> String {
+        let mut table = String::new();
+        let days: String = (1..=self.num_days)
+            .map(|day| ((day / 10) + b'0') as char)
+            .collect();
+        let units: String = (1..=self.num_days)
+            .map(|day| ((day % 10) + b'0') as char)
+            .collect();
+        writeln!(table, "{:>10} {}", "", days.replace('0', " ")).unwrap();
+        writeln!(table, "{:>10} {}", "", units).unwrap();
+        for (rank, (score, member)) in self.ranking().into_iter().enumerate() {
+            let stars: String = (1..=self.num_days)
+                .map(|day| match member.days.get(&day) {
+                    Some([Some(_), Some(_)]) => '*',
+                    Some([Some(_), None]) => '+',
+                    _ => '.',
+                })
+                .collect();
+            writeln!(
+                table,
+                "{:>3}) {:>4} {} {}",
+                rank + 1,
+                score,
+                stars,
+                member.name
+            )
+            .unwrap();
+        }
+        table
+    }
+
+    /// Star timestamps of each member, and the time between the two parts of each day
+    pub fn details(&self) -> String {
+        let mut details = String::new();
+        for (_, member) in self.ranking() {
+            writeln!(details, "{} ({} stars)", member.name, member.stars).unwrap();
+            for (day, [part_1, part_2]) in &member.days {
+                let part = |timestamp: &Option<u64>| {
+                    timestamp.map_or_else(|| "-".to_string(), format_timestamp)
+                };
+                let delta = match (part_1, part_2) {
+                    (Some(part_1), Some(part_2)) => {
+                        format!("+{}", format_duration(part_2.saturating_sub(*part_1)))
+                    }
+                    _ => String::new(),
+                };
+                let line = format!(
+                    "  Day {:>2}: {:<19}  {:<19}  {}",
+                    day,
+                    part(part_1),
+                    part(part_2),
+                    delta
+                );
+                writeln!(details, "{}", line.trim_end()).unwrap();
+            }
+        }
+        details
+    }
+}
+
+impl Puzzles {
+    /// JSON of a private leaderboard, fetched at most once per `REFRESH_INTERVAL`
+    pub fn leaderboard(&self, id: u64) -> Result<String, http::Error> {
+        let name = format!("leaderboard_{}", id);
+        let age = self
+            .cache
+            .age(&name)
+            .unwrap_or_else(|error| panic!("Failed to read cache: {}", error));
+        if age.is_some_and(|age| age < REFRESH_INTERVAL) {
+            if let Some(json) = self
+                .cache
+                .read(&name)
+                .unwrap_or_else(|error| panic!("Failed to read cache: {}", error))
+            {
+                return Ok(json);
+            }
+        }
+
+        let path = format!("/{}/leaderboard/private/view/{}.json", self.year, id);
+        let json = self.client.get(&path)?.body;
+        self.cache
+            .write(&name, &json)
+            .unwrap_or_else(|error| panic!("Failed to write cache: {}", error));
+        Ok(json)
+    }
+}
+
+/// UTC date and time of a Unix timestamp
+fn format_timestamp(timestamp: u64) -> String {
+    // Days to civil date, from http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let seconds = timestamp % 86400;
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn format_duration(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!(
+            "{}h{:02}m{:02}s",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::Cache;
+    use crate::http::mock;
+    use std::fs;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn parse_test() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.year, 2025);
+        assert_eq!(leaderboard.num_days, 12);
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(leaderboard.members[1].name, "(anonymous user #102)");
+        assert_eq!(
+            leaderboard.members[0].days[&1],
+            [Some(1764565800), Some(1764566100)]
+        );
+        assert_eq!(leaderboard.members[2].days[&2], [Some(1764651660), None]);
+    }
+
+    #[test]
+    fn local_scores_test() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        // Same scores as computed by adventofcode.com
+        let expected: Vec<u32> = serde_json::from_str::<Value>(FIXTURE).unwrap()["members"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|member| member["local_score"].as_u64().unwrap() as u32)
+            .collect();
+        assert_eq!(leaderboard.local_scores(), expected);
+        assert_eq!(
+            leaderboard
+                .ranking()
+                .iter()
+                .map(|(score, member)| (*score, member.id))
+                .collect::<Vec<_>>(),
+            [(7, 101), (5, 102), (3, 103)]
+        );
+    }
+
+    #[test]
+    fn table_test() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(
+            leaderboard.table(),
+            "           \
+             \x20        111\n           123456789012\n  \
+             1)    7 *+.......... Alice\n  \
+             2)    5 *........... (anonymous user #102)\n  \
+             3)    3 .+.......... Carol\n"
+        );
+        let details = leaderboard.details();
+        assert!(details.contains(
+            "Alice (3 stars)\n  Day  1: 2025-12-01 05:10:00  2025-12-01 05:15:00  +5m00s\n"
+        ));
+        assert!(details.contains("  Day  2: 2025-12-02 05:01:00  -"));
+    }
+
+    #[test]
+    fn format_test() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1709210096), "2024-02-29 12:34:56");
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(3725), "1h02m05s");
+    }
+
+    #[test]
+    fn refresh_interval_test() {
+        let (client, server) = mock::server(vec![mock::ok(FIXTURE)]);
+        let dir = std::env::temp_dir().join(format!("aoc-leaderboard-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let puzzles = Puzzles {
+            year: 2025,
+            client,
+            cache: Cache::new(dir),
+        };
+
+        assert_eq!(puzzles.leaderboard(42).unwrap(), FIXTURE);
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/leaderboard/private/view/42.json HTTP/1.1"));
+        // The server is gone: the leaderboard must come from the cache
+        assert_eq!(puzzles.leaderboard(42).unwrap(), FIXTURE);
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+}
@@ -0,0 +1,20 @@
+//! Code shared by every year: fetching and caching puzzle inputs and answers, running solutions,
+//! and common data structures.
+
+pub mod account;
+pub mod answer;
+pub mod article;
+pub mod bench;
+pub mod cache;
+pub mod cycle;
+pub mod http;
+pub mod interval_set;
+pub mod leaderboard;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod union_find;
+pub mod util;
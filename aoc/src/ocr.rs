@@ -0,0 +1,74 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+const CHAR_WIDTH: usize = 4;
+lazy_static! {
+    static ref CHARS: HashMap<&'static str, char> = {
+        [
+            (" ## #  ##  ######  ##  #", 'A'),
+            ("### #  #### #  ##  #### ", 'B'),
+            ("#####   ### #   #   ####", 'E'),
+            ("#####   ### #   #   #   ", 'F'),
+            (" ## #  ##   # ###  # ###", 'G'),
+            ("  ##   #   #   ##  # ## ", 'J'),
+            ("#   #   #   #   #   ####", 'L'),
+            ("### #  ##  #### #   #   ", 'P'),
+            ("### #  ##  #### # # #  #", 'R'),
+            ("#   #    # #  #   #   # ", 'Y'),
+            ("####   #  #  #  #   ####", 'Z'),
+        ]
+        .iter()
+        .cloned()
+        .collect()
+    };
+}
+
+/// Letters of an image made of 6 lines of '#' and ' ', or `None` if a glyph is unknown
+pub fn ocr(image: &str) -> Option<String> {
+    let image: Vec<_> = image.split('\n').filter(|line| !line.is_empty()).collect();
+    let height = CHARS.keys().next()?.len() / CHAR_WIDTH;
+    if image.len() != height {
+        return None;
+    }
+
+    // Lines may miss the trailing spaces of their last glyph
+    let width = image.iter().map(|line| line.len()).max()?;
+    let count = width.div_ceil(CHAR_WIDTH + 1);
+    let image: Vec<_> = image
+        .iter()
+        .map(|line| format!("{:width$}", line, width = count * (CHAR_WIDTH + 1)))
+        .collect();
+    (0..count)
+        .map(|index| {
+            let start = index * (CHAR_WIDTH + 1);
+            let image_char: String = image
+                .iter()
+                .map(|line| line.get(start..start + CHAR_WIDTH))
+                .collect::<Option<_>>()?;
+            CHARS.get(image_char.as_str()).copied()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ocr_test() {
+        let image = "\
+            ###  #   \n\
+            #  # #   \n\
+            ###  #   \n\
+            #  # #   \n\
+            #  # #   \n\
+            ###  ####";
+        assert_eq!(ocr(image), Some("BL".to_string()));
+        // Odd width, the last glyph being cut
+        assert_eq!(ocr(&image.replace("  \n", "\n")), Some("BL".to_string()));
+        assert_eq!(ocr(&image.replace(" #   \n", "\n")), None);
+        // Unknown glyph
+        assert_eq!(ocr(&image.replace("###  #", "#### #")), None);
+        assert_eq!(ocr("#\n#"), None);
+    }
+}
@@ -0,0 +1,300 @@
+//! Parsers for the usual shapes of puzzle inputs. Errors point to the line and column (both
+//! starting at 1) of the problem.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    InvalidNumber(String),
+    InvalidChar(char),
+    /// Something was expected, but the line ended or contained something else
+    Expected(&'static str),
+    /// A grid line doesn't have the same width as the first one
+    Width {
+        expected: usize,
+        found: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::InvalidNumber(number) => write!(f, "invalid number {:?}", number),
+            ErrorKind::InvalidChar(c) => write!(f, "invalid character {:?}", c),
+            ErrorKind::Expected(expected) => write!(f, "expected {}", expected),
+            ErrorKind::Width { expected, found } => {
+                write!(f, "expected {} columns, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+fn error<T>(line: usize, column: usize, kind: ErrorKind) -> Result<T> {
+    Err(Error { line, column, kind })
+}
+
+/// Every integer of a line, with its sign. A '-' is a sign only if it is not preceded by a digit,
+/// so "1-2" gives `[1, 2]`.
+pub fn integers<T: FromStr>(line_number: usize, line: &str) -> Result<Vec<T>> {
+    let bytes = line.as_bytes();
+    let mut result = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let is_sign = bytes[index] == b'-'
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_digit)
+            && (index == 0 || !bytes[index - 1].is_ascii_digit());
+        if !is_sign && !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        index += 1;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        let number = &line[start..index];
+        match number.parse() {
+            Ok(number) => result.push(number),
+            Err(_) => {
+                return error(
+                    line_number,
+                    start + 1,
+                    ErrorKind::InvalidNumber(number.to_string()),
+                )
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Lines grouped by blank lines. Each group starts with the number of its first line.
+pub fn groups(lines: impl IntoIterator<Item = String>) -> Vec<(usize, Vec<String>)> {
+    let mut groups = Vec::new();
+    let mut current: Option<(usize, Vec<String>)> = None;
+    for (index, line) in lines.into_iter().enumerate() {
+        if line.trim().is_empty() {
+            groups.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| (index + 1, Vec::new()))
+                .1
+                .push(line);
+        }
+    }
+    groups.extend(current);
+    groups
+}
+
+/// "key:value" pairs separated by whitespace, in the order of the line
+pub fn records(line_number: usize, line: &str) -> Result<Vec<(String, String)>> {
+    let mut result = Vec::new();
+    let mut column = 1;
+    for field in line.split(' ') {
+        if !field.is_empty() {
+            match field.split_once(':') {
+                Some((key, value)) => result.push((key.to_string(), value.to_string())),
+                None => return error(line_number, column, ErrorKind::Expected("key:value")),
+            }
+        }
+        column += field.len() + 1;
+    }
+    Ok(result)
+}
+
+/// Rectangular grid of cells, indexed by `[y][x]`. `cell` converts a character, or returns `None`
+/// if it is invalid.
+pub fn grid<T>(
+    lines: impl IntoIterator<Item = String>,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for (index, line) in lines.into_iter().enumerate() {
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(x, c)| {
+                cell(c).ok_or(Error {
+                    line: index + 1,
+                    column: x + 1,
+                    kind: ErrorKind::InvalidChar(c),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let kind = ErrorKind::Width {
+                    expected: first.len(),
+                    found: row.len(),
+                };
+                return error(index + 1, row.len().min(first.len()) + 1, kind);
+            }
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// A "NAME op NAME -> NAME" line, like "x00 AND y00 -> z00"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gate {
+    pub left: String,
+    pub op: String,
+    pub right: String,
+    pub output: String,
+}
+
+pub fn gate(line_number: usize, line: &str) -> Result<Gate> {
+    const EXPECTED: [&str; 5] = [
+        "an input name",
+        "an operator",
+        "an input name",
+        "'->'",
+        "an output name",
+    ];
+    let mut column = 1;
+    let words: Vec<_> = line
+        .split(' ')
+        .map(|word| {
+            let word_column = column;
+            column += word.len() + 1;
+            (word_column, word)
+        })
+        .collect();
+
+    for (index, expected) in EXPECTED.iter().enumerate() {
+        match words.get(index) {
+            Some((_, word)) if !word.is_empty() && (index != 3 || *word == "->") => {}
+            Some((column, _)) => return error(line_number, *column, ErrorKind::Expected(expected)),
+            None => return error(line_number, line.len() + 1, ErrorKind::Expected(expected)),
+        }
+    }
+    if let Some((column, _)) = words.get(EXPECTED.len()) {
+        return error(
+            line_number,
+            *column,
+            ErrorKind::Expected("the end of the line"),
+        );
+    }
+
+    Ok(Gate {
+        left: words[0].1.to_string(),
+        op: words[1].1.to_string(),
+        right: words[2].1.to_string(),
+        output: words[4].1.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn integers_test() {
+        assert_eq!(
+            integers::<i32>(1, "pos=<-3,12,0>, r=5"),
+            Ok(vec![-3, 12, 0, 5])
+        );
+        assert_eq!(integers::<u32>(1, "1-2 a: 10"), Ok(vec![1, 2, 10]));
+        assert_eq!(integers::<i32>(1, "no numbers - here"), Ok(vec![]));
+        assert_eq!(
+            integers::<u8>(3, "ab 300"),
+            Err(Error {
+                line: 3,
+                column: 4,
+                kind: ErrorKind::InvalidNumber("300".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn groups_test() {
+        assert_eq!(
+            groups(lines("abc\n\na\nb\n\n\nc\n")),
+            vec![
+                (1, vec!["abc".to_string()]),
+                (3, vec!["a".to_string(), "b".to_string()]),
+                (7, vec!["c".to_string()]),
+            ]
+        );
+        assert!(groups(lines("")).is_empty());
+    }
+
+    #[test]
+    fn records_test() {
+        assert_eq!(
+            records(1, "ecl:gry pid:860033327"),
+            Ok(vec![
+                ("ecl".to_string(), "gry".to_string()),
+                ("pid".to_string(), "860033327".to_string())
+            ])
+        );
+        let error = records(2, "ecl:gry pid").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+        assert_eq!(error.to_string(), "line 2, column 9: expected key:value");
+    }
+
+    #[test]
+    fn grid_test() {
+        let wall = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert_eq!(
+            grid(lines("#.\n.#"), wall),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(
+            grid(lines("#.\n.x"), wall).unwrap_err().kind,
+            ErrorKind::InvalidChar('x')
+        );
+        let error = grid(lines("#.\n.#.\n"), wall).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.kind,
+            ErrorKind::Width {
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+
+    #[test]
+    fn gate_test() {
+        assert_eq!(
+            gate(1, "x00 AND y00 -> z00"),
+            Ok(Gate {
+                left: "x00".to_string(),
+                op: "AND".to_string(),
+                right: "y00".to_string(),
+                output: "z00".to_string(),
+            })
+        );
+        let error = gate(4, "x00 AND y00 => z00").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 13));
+        let error = gate(4, "x00 AND y00 ->").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 15: expected an output name"
+        );
+        let error = gate(4, "x00 AND y00 -> z00 z01").unwrap_err();
+        assert_eq!(error.column, 20);
+    }
+}
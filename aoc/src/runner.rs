@@ -0,0 +1,462 @@
+use crate::account;
+use crate::answer::Answer;
+use crate::bench::{self, Baseline, Comparison, Stats};
+use crate::util::Puzzles;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::time::{Duration, Instant};
+
+pub type Input = Box<dyn Iterator<Item = String>>;
+
+pub type Part = fn(Input) -> String;
+
+pub struct Solution {
+    pub part_1: Part,
+    /// `None` while the second part is not solved
+    pub part_2: Option<Part>,
+}
+
+const USAGE: &str = "\
+Usage: run [YEAR] (--day DAY [--part PART [--submit]] | --all) [--input PATH | --accounts]
+       run [YEAR] (--day DAY [--part PART] | --all) --bench [--runs N] [--margin PERCENT]
+           [--save-baseline]
+
+Options:
+    --day DAY         Run the solution of the given day
+    --part PART       Only run the given part (1 or 2)
+    --submit          Submit the answer to adventofcode.com
+    --all             Run every solved day and print a summary table
+    --input PATH      Read the input from PATH instead of the cache, or from stdin if PATH is '-'
+    --accounts        Check the answers of every registered account instead of printing them
+    --bench           Run the solutions many times and compare them with bench_baseline.json
+    --runs N          Number of benchmark runs (default: 10)
+    --margin PERCENT  Slowdown flagged as a regression (default: 10)
+    --save-baseline   Store the benchmark results in bench_baseline.json";
+
+const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    day: Option<u8>,
+    part: Option<u8>,
+    all: bool,
+    submit: bool,
+    input: Option<String>,
+    accounts: bool,
+    bench: bool,
+    runs: Option<usize>,
+    margin: Option<f64>,
+    save_baseline: bool,
+}
+
+fn parse_options(year: u16, mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    fn value<T: std::str::FromStr>(
+        args: &mut impl Iterator<Item = String>,
+        name: &str,
+    ) -> Result<T, String> {
+        args.next()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("Invalid or missing value for {}", name))
+    }
+
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => options.day = Some(value(&mut args, "--day")?),
+            "--part" => options.part = Some(value(&mut args, "--part")?),
+            "--input" => options.input = Some(value(&mut args, "--input")?),
+            "--all" => options.all = true,
+            "--submit" => options.submit = true,
+            "--accounts" => options.accounts = true,
+            "--bench" => options.bench = true,
+            "--runs" => options.runs = Some(value(&mut args, "--runs")?),
+            "--margin" => options.margin = Some(value(&mut args, "--margin")?),
+            "--save-baseline" => options.save_baseline = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            arg if arg.parse() == Ok(year) => {}
+            other => return Err(format!("Unexpected argument: {}\n\n{}", other, USAGE)),
+        }
+    }
+
+    if options.all == options.day.is_some() {
+        return Err(USAGE.to_string());
+    }
+    if options.all && options.input.is_some() {
+        return Err("--input can't be used with --all".to_string());
+    }
+    if options.submit && options.part.is_none() {
+        return Err("--submit requires --part".to_string());
+    }
+    if options.accounts && (options.submit || options.input.is_some()) {
+        return Err("--accounts can't be used with --submit or --input".to_string());
+    }
+    if options.bench && (options.submit || options.accounts || options.input.is_some()) {
+        return Err("--bench can't be used with --submit, --accounts or --input".to_string());
+    }
+    if !options.bench
+        && (options.runs.is_some() || options.margin.is_some() || options.save_baseline)
+    {
+        return Err("--runs, --margin and --save-baseline require --bench".to_string());
+    }
+    if options.runs == Some(0) {
+        return Err("--runs should be positive".to_string());
+    }
+    if !matches!(options.part, None | Some(1) | Some(2)) {
+        return Err("--part should be 1 or 2".to_string());
+    }
+
+    Ok(options)
+}
+
+fn run_part(solution: Part, input: &[String]) -> (String, Duration) {
+    let input = Box::new(Vec::from(input).into_iter());
+    let start = Instant::now();
+    let answer = solution(input);
+    (answer, start.elapsed())
+}
+
+fn selected_parts(solution: &Solution, part: Option<u8>) -> Vec<(u8, Part)> {
+    [(1, Some(solution.part_1)), (2, solution.part_2)]
+        .iter()
+        .filter(|(n, _)| part.is_none_or(|part| part == *n))
+        .filter_map(|(n, function)| Some((*n, (*function)?)))
+        .collect()
+}
+
+struct Runner<'a> {
+    puzzles: Puzzles,
+    solutions: &'a [(u8, Solution)],
+}
+
+impl Runner<'_> {
+    fn read_input(&self, day: u8, path: Option<&str>) -> Result<Vec<String>, String> {
+        fn lines(reader: impl BufRead) -> Result<Vec<String>, String> {
+            reader
+                .lines()
+                .collect::<Result<_, _>>()
+                .map_err(|error| format!("Failed to read input: {}", error))
+        }
+
+        match path {
+            Some("-") => lines(io::stdin().lock()),
+            Some(path) => {
+                lines(BufReader::new(File::open(path).map_err(|error| {
+                    format!("Failed to open {}: {}", path, error)
+                })?))
+            }
+            None => Ok(self.puzzles.input(day).collect()),
+        }
+    }
+
+    fn find_solution(&self, day: u8) -> Result<&Solution, String> {
+        self.solutions
+            .iter()
+            .find(|(solution_day, _)| *solution_day == day)
+            .map(|(_, solution)| solution)
+            .ok_or_else(|| format!("Day {} is not solved", day))
+    }
+
+    fn selected_solutions(&self, day: Option<u8>) -> Result<Vec<(u8, &Solution)>, String> {
+        match day {
+            Some(day) => Ok(vec![(day, self.find_solution(day)?)]),
+            None => Ok(self
+                .solutions
+                .iter()
+                .map(|(day, solution)| (*day, solution))
+                .collect()),
+        }
+    }
+
+    /// Run the solutions on the input of each account and compare the results with the answers
+    /// of the account. Returns the failing days and accounts.
+    fn check_accounts(
+        &self,
+        accounts: &[(String, Puzzles)],
+        options: &Options,
+    ) -> Result<Vec<String>, String> {
+        let mut failures = Vec::new();
+        for (day, solution) in self.selected_solutions(options.day)? {
+            for (name, puzzles) in accounts {
+                let input: Vec<_> = puzzles.input(day).collect();
+                for (part, function) in selected_parts(solution, options.part) {
+                    let (answer, duration) = run_part(function, &input);
+                    let status = match puzzles.answer::<Answer>(day, part.into()) {
+                        Some(expected) if expected == answer.parse().unwrap() => "ok".to_string(),
+                        Some(expected) => {
+                            failures.push(format!("day {} part {} for {}", day, part, name));
+                            format!("FAILED, expected {}", expected)
+                        }
+                        None => "no answer".to_string(),
+                    };
+                    println!(
+                        "Day {} part {} for {}: {} ({:?}) {}",
+                        day, part, name, answer, duration, status
+                    );
+                }
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Benchmark the solutions on their input and compare them with the baseline. Returns the
+    /// regressing benchmarks.
+    fn bench(&self, baseline: &mut Baseline, options: &Options) -> Result<Vec<String>, String> {
+        let runs = options.runs.unwrap_or(DEFAULT_RUNS);
+        let margin = options
+            .margin
+            .map_or(bench::DEFAULT_MARGIN, |margin| margin / 100.);
+        let mut regressions = Vec::new();
+        for (day, solution) in self.selected_solutions(options.day)? {
+            let input = self.read_input(day, None)?;
+            for (part, function) in selected_parts(solution, options.part) {
+                let stats = Stats::measure_batched(
+                    runs,
+                    || Box::new(input.clone().into_iter()) as Input,
+                    function,
+                );
+                let name = format!("{}-{:02}-{}", self.puzzles.year, day, part);
+                let comparison = baseline.compare(&name, &stats, margin);
+                println!("Day {} part {}: {} {}", day, part, stats, comparison);
+                if let Comparison::Regression(_) = comparison {
+                    regressions.push(name.clone());
+                }
+                if options.save_baseline {
+                    baseline.insert(&name, stats);
+                }
+            }
+        }
+        Ok(regressions)
+    }
+
+    fn run_day(&self, day: u8, options: &Options) -> Result<(), String> {
+        let solution = self.find_solution(day)?;
+        let input = self.read_input(day, options.input.as_deref())?;
+        for (part, function) in selected_parts(solution, options.part) {
+            let (answer, duration) = run_part(function, &input);
+            println!("Day {} part {}: {} ({:?})", day, part, answer, duration);
+            if options.submit {
+                match self.puzzles.submit(day, part.into(), &answer) {
+                    Ok(verdict) => println!("{:?}", verdict),
+                    Err(error) => return Err(format!("Failed to submit answer: {}", error)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_all(&self, options: &Options) -> Result<(), String> {
+        let mut total = Duration::default();
+        println!("| Day | Part | {:>20} | {:>12} |", "Answer", "Time");
+        println!("|-----|------|{:-<22}|{:-<14}|", "", "");
+        for (day, solution) in self.solutions {
+            let input = self.read_input(*day, None)?;
+            for (part, function) in selected_parts(solution, options.part) {
+                let (answer, duration) = run_part(function, &input);
+                total += duration;
+                println!(
+                    "| {:>3} | {:>4} | {:>20} | {:>12} |",
+                    day,
+                    part,
+                    answer,
+                    format!("{:?}", duration)
+                );
+            }
+        }
+        println!("Total: {:?}", total);
+        Ok(())
+    }
+}
+
+/// Entry point of the `run` binary of each year, `args` not including the program name
+pub fn run(
+    year: u16,
+    solutions: &[(u8, Solution)],
+    args: impl Iterator<Item = String>,
+) -> Result<(), String> {
+    let options = parse_options(year, args)?;
+    let runner = Runner {
+        puzzles: Puzzles::new(year),
+        solutions,
+    };
+    if options.accounts {
+        return run_accounts(&runner, &options);
+    }
+    if options.bench {
+        return run_bench(&runner, &options);
+    }
+    match options.day {
+        Some(day) => runner.run_day(day, &options),
+        None => runner.run_all(&options),
+    }
+}
+
+fn run_bench(runner: &Runner, options: &Options) -> Result<(), String> {
+    let path = bench::baseline_path();
+    let mut baseline = Baseline::load(&path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    let regressions = runner.bench(&mut baseline, options)?;
+    if options.save_baseline {
+        baseline
+            .save()
+            .map_err(|error| format!("Failed to write {}: {}", path.display(), error))?;
+        Ok(())
+    } else if regressions.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Regressions: {}", regressions.join(", ")))
+    }
+}
+
+fn run_accounts(runner: &Runner, options: &Options) -> Result<(), String> {
+    let path = account::accounts_path();
+    let accounts = account::load_accounts(&path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    if accounts.is_empty() {
+        return Err(format!("No account registered in {}", path.display()));
+    }
+
+    let year = runner.puzzles.year;
+    let accounts: Vec<_> = accounts
+        .iter()
+        .map(|account| (account.name.clone(), Puzzles::for_account(year, account)))
+        .collect();
+    let failures = runner.check_accounts(&accounts, options)?;
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed: {}", failures.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        parse_options(2025, args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse_options_test() {
+        assert_eq!(
+            parse("2025 --day 5 --part 2"),
+            Ok(Options {
+                day: Some(5),
+                part: Some(2),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse("--all"),
+            Ok(Options {
+                all: true,
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse("--day 1 --input -"),
+            Ok(Options {
+                day: Some(1),
+                input: Some("-".to_string()),
+                ..Options::default()
+            })
+        );
+        assert!(parse("").is_err());
+        assert!(parse("2019 --day 1").is_err());
+        assert!(parse("--day 1 --all").is_err());
+        assert!(parse("--day 1 --part 3").is_err());
+        assert!(parse("--day 1 --submit").is_err());
+        assert!(parse("--day").is_err());
+        assert!(parse("--all --accounts").is_ok());
+        assert!(parse("--day 1 --input - --accounts").is_err());
+        assert_eq!(
+            parse("--all --bench --runs 5 --margin 2.5"),
+            Ok(Options {
+                all: true,
+                bench: true,
+                runs: Some(5),
+                margin: Some(2.5),
+                ..Options::default()
+            })
+        );
+        assert!(parse("--all --runs 5").is_err());
+        assert!(parse("--all --bench --runs 0").is_err());
+    }
+
+    #[test]
+    fn run_part_test() {
+        let solution = Solution {
+            part_1: |input| input.count().to_string(),
+            part_2: Some(|input| input.collect::<Vec<_>>().join("+")),
+        };
+        let input = vec!["1".to_string(), "2".to_string()];
+        assert_eq!(run_part(solution.part_1, &input).0, "2");
+        assert_eq!(run_part(solution.part_2.unwrap(), &input).0, "1+2");
+        assert_eq!(selected_parts(&solution, Some(2)).len(), 1);
+        assert_eq!(selected_parts(&solution, None).len(), 2);
+
+        let unsolved = Solution {
+            part_2: None,
+            ..solution
+        };
+        assert!(selected_parts(&unsolved, Some(2)).is_empty());
+        assert_eq!(selected_parts(&unsolved, None).len(), 1);
+    }
+
+    #[test]
+    fn check_accounts_test() {
+        let solutions = [(
+            1,
+            Solution {
+                part_1: |input| input.count().to_string(),
+                part_2: Some(|input| input.collect::<Vec<_>>().join("+")),
+            },
+        )];
+        let puzzles = |name: &str, files: &[(&str, &str)]| {
+            let dir =
+                std::env::temp_dir().join(format!("aoc-runner-{}-{}", name, std::process::id()));
+            let cache = crate::cache::Cache::new(dir);
+            for (file, content) in files {
+                cache.write(file, content).unwrap();
+            }
+            let client = crate::http::Client::new("http://127.0.0.1:1", None);
+            let puzzles = Puzzles {
+                year: 2025,
+                client,
+                cache,
+            };
+            (name.to_string(), puzzles)
+        };
+        let accounts = [
+            puzzles(
+                "a",
+                &[
+                    ("input_01", "1\n2"),
+                    ("answer_01_1", "2"),
+                    ("answer_01_2", "1+2"),
+                ],
+            ),
+            puzzles(
+                "b",
+                &[
+                    ("input_01", "3"),
+                    ("answer_01_1", "1"),
+                    ("answer_01_2", "4"),
+                ],
+            ),
+        ];
+        let runner = Runner {
+            puzzles: accounts[0].1.clone(),
+            solutions: &solutions,
+        };
+
+        assert_eq!(
+            runner.check_accounts(&accounts, &Options::default()),
+            Ok(vec!["day 1 part 2 for b".to_string()])
+        );
+
+        for (_, puzzles) in &accounts {
+            std::fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+        }
+    }
+}
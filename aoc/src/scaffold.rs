@@ -0,0 +1,288 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const CARGO_TOML: &str = r#"[package]
+name = "aoc-{year}"
+version = "0.1.0"
+authors = ["Benoît Zugmeyer <bzugmeyer@gmail.com>"]
+edition = "2021"
+
+[dependencies]
+aoc = { path = "../aoc" }
+"#;
+
+const LIB_RS: &str = "\
+#![allow(dead_code)]
+
+pub mod runner;
+mod util;
+";
+
+const UTIL_RS: &str = "\
+#[cfg(test)]
+use aoc::util::{self, CacheLines};
+
+pub const YEAR: u16 = {year};
+
+#[cfg(test)]
+pub fn input(day: u8) -> CacheLines {
+    util::input(YEAR, day)
+}
+
+#[cfg(test)]
+pub fn answer<T: std::str::FromStr>(day: u8, puzzle: usize) -> Option<T> {
+    util::answer(YEAR, day, puzzle)
+}
+
+#[cfg(test)]
+pub fn example(day: u8, example: usize) -> CacheLines {
+    util::example(YEAR, day, example)
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+pub fn part_example(day: u8, part: usize, example: usize) -> CacheLines {
+    util::part_example(YEAR, day, part, example)
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+pub fn example_answer<T: std::str::FromStr>(day: u8, part: usize, example: usize) -> Option<T> {
+    util::example_answer(YEAR, day, part, example)
+}
+";
+
+const RUNNER_RS: &str = "\
+use crate::util::YEAR;
+use aoc::runner::Solution;
+
+const SOLUTIONS: &[(u8, Solution)] = &[];
+
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    aoc::runner::run(YEAR, SOLUTIONS, args)
+}
+";
+
+const RUN_RS: &str = "\
+fn main() {
+    if let Err(message) = aoc_{year}::runner::run(std::env::args().skip(1)) {
+        eprintln!(\"{}\", message);
+        std::process::exit(1);
+    }
+}
+";
+
+const DAY_RS: &str = "\
+use aoc::runner::Solution;
+
+fn part_1(_input: impl Iterator<Item = String>) -> usize {
+    todo!()
+}
+
+fn part_2(_input: impl Iterator<Item = String>) -> usize {
+    todo!()
+}
+
+pub const SOLUTION: Solution = Solution {
+    part_1: |input| part_1(input).to_string(),
+    part_2: Some(|input| part_2(input).to_string()),
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+    const DAY: u8 = {day};
+
+    #[test]
+    fn test1() {
+        assert_eq!(
+            Some(part_1(util::part_example(DAY, 1, 1))),
+            util::example_answer(DAY, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(
+            Some(part_2(util::part_example(DAY, 2, 1))),
+            util::example_answer(DAY, 2, 1)
+        );
+    }
+
+    #[test]
+    fn part_1_test() {
+        assert_eq!(Some(part_1(util::input(DAY))), util::answer(DAY, 1));
+    }
+
+    #[test]
+    fn part_2_test() {
+        assert_eq!(Some(part_2(util::input(DAY))), util::answer(DAY, 2));
+    }
+}
+";
+
+/// Create a year crate with the layout of the 2025 crate, without any day
+pub fn create_year(dir: &Path, year: u16) -> io::Result<()> {
+    if dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dir.display()),
+        ));
+    }
+    let year = year.to_string();
+    fs::create_dir_all(dir.join("src/bin"))?;
+    fs::write(dir.join("Cargo.toml"), CARGO_TOML.replace("{year}", &year))?;
+    fs::write(dir.join("src/lib.rs"), LIB_RS)?;
+    fs::write(dir.join("src/util.rs"), UTIL_RS.replace("{year}", &year))?;
+    fs::write(dir.join("src/runner.rs"), RUNNER_RS)?;
+    fs::write(dir.join("src/bin/run.rs"), RUN_RS.replace("{year}", &year))?;
+    Ok(())
+}
+
+/// Create a day module in a year crate, and register it in `lib.rs` and in the runner solutions
+pub fn create_day(dir: &Path, day: u8) -> io::Result<()> {
+    let module = format!("day{:02}", day);
+    let module_path = dir.join("src").join(format!("{}.rs", module));
+    if module_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module_path.display()),
+        ));
+    }
+
+    // Prepare every file before writing any, so a failure doesn't leave the crate half updated
+    let lib_path = dir.join("src/lib.rs");
+    let lib = register_module(&fs::read_to_string(&lib_path)?, &module);
+
+    let runner_path = dir.join("src/runner.rs");
+    let runner = if runner_path.exists() {
+        let runner = fs::read_to_string(&runner_path)?;
+        Some(register_solution(&runner, day).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("No SOLUTIONS list in {}", runner_path.display()),
+            )
+        })?)
+    } else {
+        None
+    };
+
+    fs::write(&module_path, DAY_RS.replace("{day}", &day.to_string()))?;
+    fs::write(&lib_path, lib)?;
+    if let Some(runner) = runner {
+        fs::write(&runner_path, runner)?;
+    }
+    Ok(())
+}
+
+/// Insert a `mod` declaration, keeping declarations sorted by module name
+fn register_module(lib: &str, module: &str) -> String {
+    let module_name = |line: &str| {
+        line.trim_start_matches("pub ")
+            .strip_prefix("mod ")
+            .and_then(|line| line.strip_suffix(';'))
+            .map(String::from)
+    };
+    let mut lines: Vec<_> = lib.lines().map(String::from).collect();
+    let modules: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, module_name(line)?)))
+        .collect();
+    if modules.iter().any(|(_, name)| name == module) {
+        return lib.to_string();
+    }
+    let index = modules
+        .iter()
+        .find(|(_, name)| name.as_str() > module)
+        .map(|(index, _)| *index)
+        .or_else(|| modules.last().map(|(index, _)| index + 1))
+        .unwrap_or(lines.len());
+    lines.insert(index, format!("mod {};", module));
+    lines.join("\n") + "\n"
+}
+
+/// Add a day to the `SOLUTIONS` list of a runner, keeping it sorted
+fn register_solution(runner: &str, day: u8) -> Option<String> {
+    let start = runner.find("const SOLUTIONS")?;
+    let start = start + runner[start..].find("= &[")? + 4;
+    let end = start + runner[start..].find("];")?;
+
+    let mut entries: Vec<(u8, String)> = runner[start..end]
+        .split("),")
+        .filter_map(|entry| {
+            let (day, path) = entry.trim().trim_start_matches('(').split_once(',')?;
+            Some((day.trim().parse().ok()?, path.trim().to_string()))
+        })
+        .collect();
+    if entries.iter().all(|(entry_day, _)| *entry_day != day) {
+        entries.push((day, format!("crate::day{:02}::SOLUTION", day)));
+    }
+    entries.sort();
+
+    let list: String = entries
+        .iter()
+        .map(|(day, path)| format!("    ({}, {}),\n", day, path))
+        .collect();
+    Some(format!("{}\n{}{}", &runner[..start], list, &runner[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_module_test() {
+        let lib = "#![allow(dead_code)]\n\nmod day01;\nmod day03;\npub mod runner;\nmod util;\n";
+        assert_eq!(
+            register_module(lib, "day02"),
+            "#![allow(dead_code)]\n\nmod day01;\nmod day02;\nmod day03;\npub mod runner;\nmod util;\n"
+        );
+        assert_eq!(register_module(lib, "day03"), lib);
+        assert_eq!(
+            register_module(LIB_RS, "day01"),
+            "#![allow(dead_code)]\n\nmod day01;\npub mod runner;\nmod util;\n"
+        );
+    }
+
+    #[test]
+    fn register_solution_test() {
+        let runner = register_solution(RUNNER_RS, 2).unwrap();
+        assert!(runner.contains("= &[\n    (2, crate::day02::SOLUTION),\n];\n"));
+        let runner = register_solution(&runner, 1).unwrap();
+        assert!(runner.contains(
+            "= &[\n    (1, crate::day01::SOLUTION),\n    (2, crate::day02::SOLUTION),\n];\n"
+        ));
+        assert_eq!(register_solution(&runner, 1).unwrap(), runner);
+    }
+
+    #[test]
+    fn create_test() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        create_year(&dir, 2030).unwrap();
+        assert!(create_year(&dir, 2030).is_err());
+        create_day(&dir, 1).unwrap();
+        assert!(create_day(&dir, 1).is_err());
+
+        let cargo = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"aoc-2030\""));
+        let util = fs::read_to_string(dir.join("src/util.rs")).unwrap();
+        assert!(util.contains("pub const YEAR: u16 = 2030;"));
+        let day = fs::read_to_string(dir.join("src/day01.rs")).unwrap();
+        assert!(day.contains("const DAY: u8 = 1;"));
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("mod day01;\npub mod runner;"));
+
+        // Nothing is written when the runner can't be updated
+        fs::write(dir.join("src/runner.rs"), "").unwrap();
+        assert!(create_day(&dir, 2).is_err());
+        assert!(!dir.join("src/day02.rs").exists());
+        assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), lib);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
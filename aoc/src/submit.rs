@@ -0,0 +1,351 @@
+use crate::cache::Cache;
+use crate::http;
+use crate::util::Puzzles;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, we have to wait before submitting again
+    RateLimited(Duration),
+    /// The part is already solved, or the first part is not solved yet
+    WrongLevel,
+    /// The part is already solved with another answer, the cached one
+    AlreadySolved(String),
+    /// Unrecognized response, containing the text of the response article, or the submission
+    /// failed in a way that doesn't tell if the answer was received
+    Unknown(String),
+}
+
+impl Verdict {
+    fn from_record(record: &str) -> Option<Self> {
+        match record {
+            "incorrect" => Some(Self::Incorrect),
+            "too_high" => Some(Self::TooHigh),
+            "too_low" => Some(Self::TooLow),
+            _ => None,
+        }
+    }
+
+    fn to_record(&self) -> Option<&'static str> {
+        match self {
+            Self::Incorrect => Some("incorrect"),
+            Self::TooHigh => Some("too_high"),
+            Self::TooLow => Some("too_low"),
+            _ => None,
+        }
+    }
+}
+
+/// Submit an answer to adventofcode.com. Wrong answers are remembered in the cache so they are
+/// never submitted twice, and the accepted answer is stored like the ones fetched by
+/// `util::answer`.
+pub fn submit(year: u16, day: u8, part: usize, answer: &str) -> Result<Verdict, http::Error> {
+    Puzzles::new(year).submit(day, part, answer)
+}
+
+impl Puzzles {
+    pub fn submit(&self, day: u8, part: usize, answer: &str) -> Result<Verdict, http::Error> {
+        let answer_name = format!("answer_{:02}_{}", day, part);
+        if let Some(known_answer) = read_cache(&self.cache, &answer_name) {
+            let known_answer = known_answer.trim();
+            return Ok(if known_answer == answer {
+                Verdict::Correct
+            } else {
+                Verdict::AlreadySolved(known_answer.to_string())
+            });
+        }
+
+        let wrong_name = format!("wrong_{:02}_{}", day, part);
+        let wrong_records = read_cache(&self.cache, &wrong_name).unwrap_or_default();
+        let wrong_answers = parse_wrong_answers(&wrong_records);
+        if let Some(verdict) = known_verdict(&wrong_answers, answer) {
+            return Ok(verdict);
+        }
+
+        let body = format!("level={}&answer={}", part, url_encode(answer));
+        let response = match self
+            .client
+            .post_form(&format!("/{}/day/{}/answer", self.year, day), &body)
+        {
+            // The answer may have been received, don't submit it again nor remember a verdict
+            Err(
+                error @ (http::Error::Io(_)
+                | http::Error::InvalidResponse(_)
+                | http::Error::Status(500.., _)),
+            ) => {
+                return Ok(Verdict::Unknown(format!(
+                    "{}, check the puzzle page to know if the answer was received",
+                    error
+                )))
+            }
+            response => response?,
+        };
+        let verdict = parse_response(&response.body);
+
+        if verdict == Verdict::Correct {
+            write_cache(&self.cache, &answer_name, answer);
+        } else if let Some(record) = verdict.to_record() {
+            let records = format!("{}{} {}\n", wrong_records, answer, record);
+            write_cache(&self.cache, &wrong_name, &records);
+        }
+
+        Ok(verdict)
+    }
+}
+
+fn read_cache(cache: &Cache, name: &str) -> Option<String> {
+    cache
+        .read(name)
+        .unwrap_or_else(|error| panic!("Failed to read cache: {}", error))
+}
+
+fn write_cache(cache: &Cache, name: &str, content: &str) {
+    cache
+        .write(name, content)
+        .unwrap_or_else(|error| panic!("Failed to write cache: {}", error));
+}
+
+fn parse_wrong_answers(records: &str) -> Vec<(String, Verdict)> {
+    records
+        .lines()
+        .filter_map(|line| {
+            let (answer, record) = line.rsplit_once(' ')?;
+            Some((answer.to_string(), Verdict::from_record(record)?))
+        })
+        .collect()
+}
+
+/// Find the verdict of an answer from previous wrong answers, without submitting it. Numeric
+/// answers are also compared to the previous "too high" and "too low" bounds.
+fn known_verdict(wrong_answers: &[(String, Verdict)], answer: &str) -> Option<Verdict> {
+    let number: Option<i128> = answer.parse().ok();
+    wrong_answers.iter().find_map(|(wrong_answer, verdict)| {
+        if wrong_answer == answer {
+            return Some(verdict.clone());
+        }
+        let (number, wrong_number) = (number?, wrong_answer.parse::<i128>().ok()?);
+        match verdict {
+            Verdict::TooHigh if number >= wrong_number => Some(Verdict::TooHigh),
+            Verdict::TooLow if number <= wrong_number => Some(Verdict::TooLow),
+            _ => None,
+        }
+    })
+}
+
+fn parse_response(html: &str) -> Verdict {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(&text).unwrap_or_default())
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// Text content of the first `<article>` element of the page
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(html, |(_, rest)| {
+            rest.split_once("</article>")
+                .map_or(rest, |(article, _)| article)
+        });
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in article.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Parse "You have 1m 23s left to wait"
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .try_fold(Duration::default(), |total, token| {
+            let (value, unit) = token.split_at(token.len() - 1);
+            let value: u64 = value.parse().ok()?;
+            let seconds = match unit {
+                "s" => value,
+                "m" => value * 60,
+                "h" => value * 3600,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(seconds))
+        })
+}
+
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{:02X}", byte)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock;
+    use std::fs;
+    use std::thread::JoinHandle;
+
+    fn response_page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    fn mock_puzzles(name: &str, page: &str) -> (Puzzles, JoinHandle<Vec<String>>) {
+        let (client, server) = mock::server(vec![mock::ok(&response_page(page))]);
+        let cache_dir =
+            std::env::temp_dir().join(format!("aoc-submit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let puzzles = Puzzles {
+            year: 2025,
+            client,
+            cache: Cache::new(cache_dir),
+        };
+        (puzzles, server)
+    }
+
+    #[test]
+    fn parse_response_test() {
+        assert_eq!(
+            parse_response(&response_page(
+                "That's the right answer!  You are one gold star closer to decorating."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_response(&response_page(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_response(&response_page(
+                "That's not the right answer.  If you're stuck, make sure you're using the full \
+                 input data"
+            )),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_response(&response_page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 23s left to wait. <a href=\"/2025/day/1\">\
+                 [Return to Day 1]</a>"
+            )),
+            Verdict::RateLimited(Duration::from_secs(83))
+        );
+        assert_eq!(
+            parse_response(&response_page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::WrongLevel
+        );
+    }
+
+    #[test]
+    fn known_verdict_test() {
+        let wrong_answers = vec![
+            ("100".to_string(), Verdict::TooHigh),
+            ("10".to_string(), Verdict::TooLow),
+            ("abc".to_string(), Verdict::Incorrect),
+        ];
+        assert_eq!(
+            known_verdict(&wrong_answers, "abc"),
+            Some(Verdict::Incorrect)
+        );
+        assert_eq!(known_verdict(&wrong_answers, "150"), Some(Verdict::TooHigh));
+        assert_eq!(known_verdict(&wrong_answers, "3"), Some(Verdict::TooLow));
+        assert_eq!(known_verdict(&wrong_answers, "50"), None);
+        assert_eq!(known_verdict(&wrong_answers, "def"), None);
+    }
+
+    #[test]
+    fn submit_wrong_answer_test() {
+        let (puzzles, server) = mock_puzzles(
+            "wrong",
+            "That's not the right answer; your answer is too low.",
+        );
+
+        assert_eq!(puzzles.submit(3, 2, "42").unwrap(), Verdict::TooLow);
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/3/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=42"));
+
+        // The server is gone: the verdict must come from the cache
+        assert_eq!(puzzles.submit(3, 2, "42").unwrap(), Verdict::TooLow);
+        assert_eq!(puzzles.submit(3, 2, "12").unwrap(), Verdict::TooLow);
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+
+    #[test]
+    fn submit_right_answer_test() {
+        let (puzzles, server) = mock_puzzles("right", "That's the right answer!");
+
+        assert_eq!(puzzles.submit(3, 1, "ABC DEF").unwrap(), Verdict::Correct);
+        assert!(server.join().unwrap()[0].ends_with("level=1&answer=ABC%20DEF"));
+        assert_eq!(
+            fs::read_to_string(puzzles.cache.dir.join("answer_03_1")).unwrap(),
+            "ABC DEF"
+        );
+        assert_eq!(
+            puzzles.submit(3, 1, "ABC").unwrap(),
+            Verdict::AlreadySolved("ABC DEF".to_string())
+        );
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+
+    #[test]
+    fn submit_server_error_test() {
+        let (client, server) = mock::server(vec![mock::response("502 Bad Gateway", "")]);
+        let puzzles = Puzzles {
+            year: 2025,
+            client,
+            cache: Cache::new(
+                std::env::temp_dir()
+                    .join(format!("aoc-submit-server-error-{}", std::process::id())),
+            ),
+        };
+
+        assert!(matches!(
+            puzzles.submit(3, 1, "42").unwrap(),
+            Verdict::Unknown(message) if message.contains("check the puzzle page")
+        ));
+        assert_eq!(server.join().unwrap().len(), 1);
+        assert!(!puzzles.cache.dir.join("wrong_03_1").exists());
+        assert!(!puzzles.cache.dir.join("answer_03_1").exists());
+    }
+}
@@ -0,0 +1,122 @@
+/// Disjoint set of elements `0..len`, with path compression and union by size
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    component_count: usize,
+    final_union: Option<(usize, usize)>,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            component_count: len,
+            final_union: None,
+        }
+    }
+
+    /// Return the representative element of the component containing `element`
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+        while current != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+
+        root
+    }
+
+    /// Merge the components containing `a` and `b`. Return false if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+
+        let (large, small) = if self.sizes[root_a] >= self.sizes[root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.component_count -= 1;
+        if self.component_count == 1 {
+            self.final_union = Some((a, b));
+        }
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Return the sizes of the `k` largest components, largest first
+    pub fn largest_component_sizes(&self, k: usize) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self
+            .parents
+            .iter()
+            .enumerate()
+            .filter(|(element, parent)| element == *parent)
+            .map(|(root, _)| self.sizes[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.truncate(k);
+        sizes
+    }
+
+    /// Return the arguments of the union that joined the last two components, if every element is
+    /// now connected
+    pub fn final_union(&self) -> Option<(usize, usize)> {
+        self.final_union
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_test() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.component_count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 1));
+        assert!(!sets.union(0, 2));
+        assert!(sets.union(4, 5));
+        assert!(sets.connected(0, 2));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.component_size(1), 3);
+        assert_eq!(sets.component_size(3), 1);
+        assert_eq!(sets.largest_component_sizes(2), vec![3, 2]);
+        assert_eq!(sets.largest_component_sizes(10), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn final_union_test() {
+        let mut sets = UnionFind::new(4);
+        sets.union(0, 1);
+        sets.union(2, 3);
+        assert_eq!(sets.final_union(), None);
+        sets.union(3, 1);
+        assert_eq!(sets.final_union(), Some((3, 1)));
+        assert_eq!(sets.component_count(), 1);
+    }
+}
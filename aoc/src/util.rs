@@ -0,0 +1,330 @@
+use crate::account::{self, Account};
+use crate::article;
+use crate::cache::Cache;
+use crate::http::{self, Client};
+
+use std::str::FromStr;
+
+pub const CACHE_DIR: &str = "cache";
+
+pub type CacheLines = std::vec::IntoIter<String>;
+
+/// Puzzles of a year, fetched from adventofcode.com and stored in a cache directory
+#[derive(Debug, Clone)]
+pub struct Puzzles {
+    pub year: u16,
+    pub client: Client,
+    pub cache: Cache,
+}
+
+impl Puzzles {
+    /// Use the client and the cache key configured by the environment, and the `cache` directory
+    /// of the current working directory, namespaced by the account of `AOC_SESSION`. If
+    /// `AOC_PROFILE` is set, use this registered account instead. Without any of them, the cache
+    /// directory is used as is. Entries missing from the account directory are read from the
+    /// `cache` directory itself.
+    pub fn new(year: u16) -> Self {
+        let account = account::profile().or_else(|| {
+            std::env::var("AOC_SESSION")
+                .ok()
+                .map(|session| Account::from_session(&session))
+        });
+        match account {
+            Some(account) => Self::for_account(year, &account),
+            None => Self {
+                year,
+                client: Client::from_env(),
+                cache: Cache::from_env(CACHE_DIR),
+            },
+        }
+    }
+
+    pub fn for_account(year: u16, account: &Account) -> Self {
+        Self {
+            year,
+            client: Client::from_env().with_session(&account.session),
+            cache: Cache::from_env(CACHE_DIR).subdir(&account.name),
+        }
+    }
+
+    pub fn input(&self, day: u8) -> CacheLines {
+        from_cache(&self.cache, &format!("input_{:02}", day), || {
+            Some(self.fetch(&format!("/{}/day/{}/input", self.year, day)))
+        })
+        .unwrap()
+    }
+
+    pub fn answer<T: FromStr>(&self, day: u8, puzzle: usize) -> Option<T> {
+        from_cache(
+            &self.cache,
+            &format!("answer_{:02}_{}", day, puzzle),
+            || {
+                self.fetch_day_page(day)
+                    .lines()
+                    .filter_map(|line| {
+                        line.split(['<', '>'])
+                            .skip_while(|s| s != &"Your puzzle answer was ")
+                            .nth(2)
+                            .map(std::string::ToString::to_string)
+                    })
+                    .nth(puzzle - 1)
+                    .map(|answer| vec![answer])
+            },
+        )
+        // Get the first line
+        .and_then(|mut cache| cache.next())
+        // Parse the number
+        .and_then(|answer| answer.parse().ok())
+    }
+
+    /// Lines of the nth code block of the day page, regardless of the part it belongs to
+    pub fn example(&self, day: u8, example: usize) -> CacheLines {
+        from_cache(
+            &self.cache,
+            &format!("example_{:02}_{}", day, example),
+            || {
+                article::parse_all_examples(&self.fetch_day_page(day))
+                    .into_iter()
+                    .nth(example - 1)
+                    .map(|example| example.lines)
+            },
+        )
+        .unwrap()
+    }
+
+    /// Lines of the nth code block of the given part description
+    pub fn part_example(&self, day: u8, part: usize, example: usize) -> CacheLines {
+        from_cache(
+            &self.cache,
+            &format!("part_example_{:02}_{}_{}", day, part, example),
+            || {
+                self.find_part_example(day, part, example)
+                    .map(|example| example.lines)
+            },
+        )
+        .unwrap()
+    }
+
+    /// Emphasized answer following the nth code block of the given part description
+    pub fn example_answer<T: FromStr>(&self, day: u8, part: usize, example: usize) -> Option<T> {
+        from_cache(
+            &self.cache,
+            &format!("example_answer_{:02}_{}_{}", day, part, example),
+            || {
+                self.find_part_example(day, part, example)
+                    .and_then(|example| example.answer)
+                    .map(|answer| vec![answer])
+            },
+        )
+        .and_then(|mut cache| cache.next())
+        .and_then(|answer| answer.parse().ok())
+    }
+
+    fn find_part_example(&self, day: u8, part: usize, example: usize) -> Option<article::Example> {
+        article::parse_parts(&self.fetch_day_page(day))
+            .into_iter()
+            .nth(part - 1)?
+            .into_iter()
+            .nth(example - 1)
+    }
+
+    /// Fetch the input and the examples of a day into the cache, returning the number of examples
+    pub fn prefetch(&self, day: u8) -> Result<usize, http::Error> {
+        let input = self
+            .client
+            .get(&format!("/{}/day/{}/input", self.year, day))?;
+        from_cache(&self.cache, &format!("input_{:02}", day), || {
+            Some(input.body.lines().map(String::from).collect())
+        });
+
+        let page = self.client.get(&format!("/{}/day/{}", self.year, day))?;
+        let examples = article::parse_all_examples(&page.body);
+        for (index, example) in examples.iter().enumerate() {
+            from_cache(
+                &self.cache,
+                &format!("example_{:02}_{}", day, index + 1),
+                || Some(example.lines.clone()),
+            );
+        }
+        // Examples and answers of each part, as read by the tests of the day template
+        for (part, examples) in article::parse_parts(&page.body).iter().enumerate() {
+            for (index, example) in examples.iter().enumerate() {
+                let suffix = format!("{:02}_{}_{}", day, part + 1, index + 1);
+                from_cache(&self.cache, &format!("part_example_{}", suffix), || {
+                    Some(example.lines.clone())
+                });
+                if let Some(answer) = &example.answer {
+                    from_cache(&self.cache, &format!("example_answer_{}", suffix), || {
+                        Some(vec![answer.clone()])
+                    });
+                }
+            }
+        }
+        Ok(examples.len())
+    }
+
+    fn fetch_day_page(&self, day: u8) -> String {
+        self.fetch(&format!("/{}/day/{}", self.year, day))
+            .join("\n")
+    }
+
+    fn fetch(&self, path: &str) -> Vec<String> {
+        let response = self
+            .client
+            .get(path)
+            .unwrap_or_else(|error| panic!("Failed to fetch {}: {}", path, error));
+
+        response.body.lines().map(String::from).collect()
+    }
+}
+
+pub fn input(year: u16, day: u8) -> CacheLines {
+    Puzzles::new(year).input(day)
+}
+
+pub fn answer<T: FromStr>(year: u16, day: u8, puzzle: usize) -> Option<T> {
+    Puzzles::new(year).answer(day, puzzle)
+}
+
+pub fn example(year: u16, day: u8, example: usize) -> CacheLines {
+    Puzzles::new(year).example(day, example)
+}
+
+pub fn part_example(year: u16, day: u8, part: usize, example: usize) -> CacheLines {
+    Puzzles::new(year).part_example(day, part, example)
+}
+
+pub fn example_answer<T: FromStr>(year: u16, day: u8, part: usize, example: usize) -> Option<T> {
+    Puzzles::new(year).example_answer(day, part, example)
+}
+
+fn from_cache<T: FnOnce() -> Option<Vec<String>>>(
+    cache: &Cache,
+    name: &str,
+    factory: T,
+) -> Option<CacheLines> {
+    let content = match cache
+        .read(name)
+        .unwrap_or_else(|error| panic!("Failed to read cache: {}", error))
+    {
+        Some(content) => content,
+        None => {
+            let content = factory()?.join("\n");
+            // Populate cache
+            cache
+                .write(name, &content)
+                .unwrap_or_else(|error| panic!("Failed to write cache: {}", error));
+            content
+        }
+    };
+
+    Some(
+        content
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .into_iter(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock;
+    use std::fs;
+    use std::panic;
+    use std::path::PathBuf;
+    use std::thread::JoinHandle;
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-util-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn mock_puzzles(name: &str, responses: Vec<String>) -> (Puzzles, JoinHandle<Vec<String>>) {
+        let (client, server) = mock::server(responses);
+        let puzzles = Puzzles {
+            year: 2025,
+            client,
+            cache: Cache::new(temp_cache_dir(name)),
+        };
+        (puzzles, server)
+    }
+
+    #[test]
+    fn input_test() {
+        let (puzzles, server) = mock_puzzles("input", vec![mock::ok("1\n2\n3\n")]);
+
+        assert_eq!(puzzles.input(4).collect::<Vec<_>>(), ["1", "2", "3"]);
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/4/input HTTP/1.1\r\n"));
+
+        // The server is gone: the input must come from the cache
+        assert_eq!(puzzles.input(4).collect::<Vec<_>>(), ["1", "2", "3"]);
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+
+    #[test]
+    fn logged_out_is_not_cached_test() {
+        let (puzzles, server) = mock_puzzles(
+            "logged-out",
+            vec![mock::response(
+                "400 Bad Request",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            )],
+        );
+
+        let result = panic::catch_unwind(|| puzzles.input(4).count());
+        assert!(result.is_err());
+        server.join().unwrap();
+        assert!(!puzzles.cache.dir.join("input_04").exists());
+    }
+
+    #[test]
+    fn part_example_test() {
+        let page = "<article class=\"day-desc\"><pre><code>1\n2\n</code></pre>\n\
+                    <p>Answer: <code><em>3</em></code></p></article>\n\
+                    <article class=\"day-desc\"><pre><code>a &lt; b\n</code></pre>\n\
+                    <p>Answer: <code><em>true</em></code></p></article>";
+        let (puzzles, server) = mock_puzzles("example", vec![mock::ok(page), mock::ok(page)]);
+
+        assert_eq!(puzzles.part_example(4, 2, 1).collect::<Vec<_>>(), ["a < b"]);
+        assert_eq!(puzzles.example_answer(4, 1, 1), Some(3));
+        server.join().unwrap();
+        assert_eq!(puzzles.example_answer(4, 1, 1), Some(3));
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+
+    #[test]
+    fn prefetch_test() {
+        let page = "<article class=\"day-desc\"><pre><code>1\n2\n</code></pre>\n\
+                    <pre><code>3\n</code></pre><p>gives <code><em>6</em></code>.</p></article>";
+        let (puzzles, server) = mock_puzzles("prefetch", vec![mock::ok("4\n5\n"), mock::ok(page)]);
+
+        assert_eq!(puzzles.prefetch(4).unwrap(), 2);
+        server.join().unwrap();
+        assert_eq!(puzzles.input(4).collect::<Vec<_>>(), ["4", "5"]);
+        assert_eq!(puzzles.example(4, 2).collect::<Vec<_>>(), ["3"]);
+        // Read from the cache, the server is gone
+        assert_eq!(puzzles.part_example(4, 1, 2).collect::<Vec<_>>(), ["3"]);
+        assert_eq!(puzzles.example_answer(4, 1, 2), Some(6));
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+
+    #[test]
+    fn answer_test() {
+        let page = "<p>Your puzzle answer was <code>1234</code>.</p>\n\
+                    <p>Your puzzle answer was <code>5678</code>.</p>";
+        let (puzzles, server) = mock_puzzles("answer", vec![mock::ok(page)]);
+
+        assert_eq!(puzzles.answer(4, 2), Some(5678));
+        server.join().unwrap();
+        assert_eq!(puzzles.answer(4, 2), Some(5678));
+
+        fs::remove_dir_all(&puzzles.cache.dir).unwrap();
+    }
+}